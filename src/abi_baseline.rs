@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use std::collections;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::summarize::{BinaryType, ElfSummary, SymbolBinding, SymbolType, VersionedSymbol};
+
+/// A defined symbol, as recorded in the `defined_symbols` of a JSON summary
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BaselineSymbol {
+    pub name: String,
+    pub version: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: SymbolType,
+    pub binding: SymbolBinding,
+    pub size: u64,
+}
+
+impl BaselineSymbol {
+    fn key(&self) -> VersionedSymbol {
+        VersionedSymbol {
+            name: self.name.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+/// The part of a JSON summary (from `--format json`) that the ABI is compared against
+///
+/// The other fields of the summary are ignored
+#[derive(Clone, Debug, Deserialize)]
+pub struct BaselineBinary {
+    pub path: PathBuf,
+    pub defined_symbols: Vec<BaselineSymbol>,
+}
+
+/// A change in the ABI of a binary relative to its baseline
+#[derive(Debug, Serialize)]
+#[serde(tag = "change", rename_all = "kebab-case")]
+pub enum AbiChange {
+    /// A symbol is defined now, but not in the baseline
+    Added { symbol: BaselineSymbol },
+    /// A symbol in the baseline is no longer defined
+    Removed { symbol: BaselineSymbol },
+    /// The type, binding, or size of a symbol changed
+    Changed {
+        baseline: BaselineSymbol,
+        current: BaselineSymbol,
+    },
+}
+
+impl std::fmt::Display for AbiChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AbiChange::Added { symbol } => write!(f, "added {}", symbol.key()),
+            AbiChange::Removed { symbol } => write!(f, "removed {}", symbol.key()),
+            AbiChange::Changed { baseline, current } => {
+                let mut changes = Vec::new();
+                if baseline.type_ != current.type_ {
+                    changes.push(format!("type {:?} -> {:?}", baseline.type_, current.type_));
+                }
+                if baseline.binding != current.binding {
+                    changes.push(format!(
+                        "binding {:?} -> {:?}",
+                        baseline.binding, current.binding
+                    ));
+                }
+                if size_changed(baseline, current) {
+                    changes.push(format!("size {} -> {}", baseline.size, current.size));
+                }
+                write!(f, "changed {}: {}", current.key(), changes.join(", "))
+            }
+        }
+    }
+}
+
+/// Sizes are only compared if both are known (symbols without a size, e.g.,
+/// hand-written assembly functions, have a size of zero)
+fn size_changed(baseline: &BaselineSymbol, current: &BaselineSymbol) -> bool {
+    baseline.size != 0 && current.size != 0 && baseline.size != current.size
+}
+
+pub fn read(path: &Path) -> anyhow::Result<Vec<BaselineBinary>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Find the baseline for `summary`
+///
+/// A baseline with a single binary is used for any input, since the path
+/// usually changes between versions (e.g., `libfoo.so.1.2` to `libfoo.so.1.3`);
+/// otherwise, the binary is matched by path
+pub fn find<'a>(
+    baseline: &'a [BaselineBinary],
+    summary: &ElfSummary,
+) -> Option<&'a BaselineBinary> {
+    match baseline {
+        [only] => Some(only),
+        _ => baseline.iter().find(|b| b.path == summary.filename),
+    }
+}
+
+/// The symbols that make up the ABI of `summary` (i.e., the non-local
+/// symbols in its dynamic symbol table)
+fn abi_symbols(summary: &ElfSummary) -> collections::BTreeMap<VersionedSymbol, BaselineSymbol> {
+    let defined = match &summary.binary_type {
+        BinaryType::Static => return collections::BTreeMap::new(),
+        BinaryType::Dynamic(dyn_data) => &dyn_data.provided_dynamic_symbols,
+    };
+    defined
+        .iter()
+        .filter(|d| d.binding != SymbolBinding::Local)
+        .map(|d| {
+            (
+                d.symbol.clone(),
+                BaselineSymbol {
+                    name: d.symbol.name.clone(),
+                    version: d.symbol.version.clone(),
+                    type_: d.type_,
+                    binding: d.binding,
+                    size: d.size,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Compare the ABI of `summary` with its baseline, matching symbols by name and version
+pub fn compare(baseline: &BaselineBinary, summary: &ElfSummary) -> Vec<AbiChange> {
+    let current = abi_symbols(summary);
+    let previous: collections::BTreeMap<VersionedSymbol, &BaselineSymbol> = baseline
+        .defined_symbols
+        .iter()
+        .filter(|s| s.binding != SymbolBinding::Local)
+        .map(|s| (s.key(), s))
+        .collect();
+
+    let mut changes = Vec::new();
+    for (key, sym) in &current {
+        match previous.get(key) {
+            None => changes.push(AbiChange::Added {
+                symbol: sym.clone(),
+            }),
+            Some(prev)
+                if prev.type_ != sym.type_
+                    || prev.binding != sym.binding
+                    || size_changed(prev, sym) =>
+            {
+                changes.push(AbiChange::Changed {
+                    baseline: (*prev).clone(),
+                    current: sym.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, sym) in &previous {
+        if !current.contains_key(key) {
+            changes.push(AbiChange::Removed {
+                symbol: (*sym).clone(),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summarize;
+
+    fn fixture() -> ElfSummary {
+        summarize::summarize_path(&PathBuf::from("tests/fixtures/libfixture.so")).unwrap()
+    }
+
+    /// A baseline with the current ABI of `summary`
+    fn baseline_of(summary: &ElfSummary) -> BaselineBinary {
+        BaselineBinary {
+            path: summary.filename.clone(),
+            defined_symbols: abi_symbols(summary).into_values().collect(),
+        }
+    }
+
+    fn symbol<'a>(baseline: &'a mut BaselineBinary, name: &str) -> &'a mut BaselineSymbol {
+        baseline
+            .defined_symbols
+            .iter_mut()
+            .find(|s| s.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn identical_abi_has_no_changes() {
+        let summary = fixture();
+        assert!(compare(&baseline_of(&summary), &summary).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_symbols() {
+        let summary = fixture();
+        let mut baseline = baseline_of(&summary);
+        baseline
+            .defined_symbols
+            .retain(|s| s.name != "fixture_value");
+        let mut old = baseline.defined_symbols[0].clone();
+        old.name = String::from("fixture_old");
+        baseline.defined_symbols.push(old);
+
+        let changes = compare(&baseline, &summary);
+        assert_eq!(changes.len(), 2);
+        assert!(
+            matches!(&changes[0], AbiChange::Added { symbol } if symbol.name == "fixture_value")
+        );
+        assert!(
+            matches!(&changes[1], AbiChange::Removed { symbol } if symbol.name == "fixture_old")
+        );
+    }
+
+    #[test]
+    fn type_and_binding_changes() {
+        let summary = fixture();
+        let mut baseline = baseline_of(&summary);
+        symbol(&mut baseline, "fixture_add").type_ = SymbolType::Object;
+        symbol(&mut baseline, "fixture_value").binding = SymbolBinding::Weak;
+
+        let changes = compare(&baseline, &summary);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0].to_string(),
+            "changed fixture_add: type Object -> Func"
+        );
+        assert_eq!(
+            changes[1].to_string(),
+            "changed fixture_value: binding Weak -> Global"
+        );
+    }
+
+    #[test]
+    fn sizes_are_only_compared_when_both_are_known() {
+        let summary = fixture();
+        let mut baseline = baseline_of(&summary);
+        symbol(&mut baseline, "fixture_add").size = 0;
+        assert!(compare(&baseline, &summary).is_empty());
+
+        symbol(&mut baseline, "fixture_value").size = 8;
+        let changes = compare(&baseline, &summary);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "changed fixture_value: size 8 -> 4");
+    }
+
+    #[test]
+    fn single_baseline_matches_any_path() {
+        let summary = fixture();
+        let mut baseline = baseline_of(&summary);
+        baseline.path = PathBuf::from("libfixture.so.1.0");
+        let found = find(std::slice::from_ref(&baseline), &summary).unwrap();
+        assert_eq!(found.path, baseline.path);
+    }
+
+    #[test]
+    fn multiple_baselines_match_by_path() {
+        let summary = fixture();
+        let mut other = baseline_of(&summary);
+        other.path = PathBuf::from("libother.so");
+        let baselines = vec![other.clone(), baseline_of(&summary)];
+        let found = find(&baselines, &summary).unwrap();
+        assert_eq!(found.path, summary.filename);
+
+        assert!(find(&[other.clone(), other], &summary).is_none());
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections;
+use std::fs;
+use std::path::Path;
+
+use crate::summarize::{BinaryType, ElfSummary, VersionedSymbol};
+
+/// A set of symbols that binaries are allowed to reference
+///
+/// An entry without a version allows any version of the symbol (including an
+/// unversioned reference), while a versioned entry only allows that version.
+pub struct Allowlist {
+    entries: collections::HashMap<String, Vec<Option<String>>>,
+}
+
+impl Allowlist {
+    /// Parse an allowlist with one `name[@version]` entry per line
+    ///
+    /// Blank lines and lines starting with `#` are ignored.  The `name@@version`
+    /// syntax for default versions is accepted as a synonym for `name@version`.
+    pub fn parse(contents: &str) -> Self {
+        let mut entries: collections::HashMap<String, Vec<Option<String>>> =
+            collections::HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, version) = match line.split_once('@') {
+                None => (line, None),
+                Some((name, version)) => (name, Some(version.trim_start_matches('@').to_string())),
+            };
+            entries.entry(name.to_string()).or_default().push(version);
+        }
+        Allowlist { entries }
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        Ok(Allowlist::parse(&fs::read_to_string(path)?))
+    }
+
+    pub fn allows(&self, sym: &VersionedSymbol) -> bool {
+        self.entries
+            .get(&sym.name)
+            .is_some_and(|versions| versions.iter().any(|v| v.is_none() || *v == sym.version))
+    }
+}
+
+/// The dynamic symbols referenced by the binary that are not in the allowlist
+pub fn violations<'a>(summ: &'a ElfSummary, allowlist: &Allowlist) -> Vec<&'a VersionedSymbol> {
+    match &summ.binary_type {
+        BinaryType::Static => Vec::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data
+            .dynamic_symbol_refs
+            .iter()
+            .map(|r| &r.symbol)
+            .filter(|sym| !allowlist.allows(sym))
+            .collect(),
+    }
+}
@@ -0,0 +1,122 @@
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ArchiveError {
+    #[error("{1} is not a member of {0:?}")]
+    MissingMember(PathBuf, String),
+    #[error("Too many levels of symbolic links resolving {1} in {0:?}")]
+    SymlinkLoop(PathBuf, String),
+    #[error("{0:?} is a squashfs image, which is not supported yet (only tar archives are)")]
+    UnsupportedSquashfs(PathBuf),
+}
+
+/// The file extensions of the archives that members can be read from
+const ARCHIVE_EXTENSIONS: [&str; 4] = [".tar", ".tar.gz", ".tgz", ".squashfs"];
+
+/// The maximum number of symbolic links to follow when reading a member
+const MAX_SYMLINK_DEPTH: usize = 16;
+
+/// Split a path of the form `image.tar:/usr/bin/foo` into the archive and member paths
+///
+/// Returns `None` if the path does not refer to a member of an existing archive
+pub fn split_archive_path(path: &Path) -> Option<(PathBuf, String)> {
+    let path_str = path.to_str()?;
+    for (idx, _) in path_str.match_indices(':') {
+        let archive = &path_str[..idx];
+        if ARCHIVE_EXTENSIONS.iter().any(|ext| archive.ends_with(ext))
+            && Path::new(archive).is_file()
+        {
+            return Some((PathBuf::from(archive), path_str[idx + 1..].to_string()));
+        }
+    }
+    None
+}
+
+/// Normalize a member path so that `/usr/lib/../bin/foo`, `./usr/bin/foo`, and
+/// `usr/bin/foo` all compare equal
+fn normalize_member(member: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in member.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                res.pop();
+            }
+            Component::Normal(c) => res.push(c),
+        }
+    }
+    res
+}
+
+fn open_archive(archive: &Path) -> anyhow::Result<tar::Archive<Box<dyn Read>>> {
+    let mut file = fs::File::open(archive)?;
+    let mut magic = [0; 4];
+    if file.read_exact(&mut magic).is_ok() && &magic == b"hsqs" {
+        return Err(anyhow::Error::new(ArchiveError::UnsupportedSquashfs(
+            archive.to_path_buf(),
+        )));
+    }
+    let file = fs::File::open(archive)?;
+    let name = archive.to_string_lossy();
+    let reader: Box<dyn Read> = if name.ends_with(".gz") || name.ends_with(".tgz") {
+        Box::new(GzDecoder::new(io::BufReader::new(file)))
+    } else {
+        Box::new(io::BufReader::new(file))
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+/// Read the contents of a member of a tar archive (which may be gzip compressed)
+///
+/// Symbolic links within the archive are followed (e.g., from a library's
+/// soname to the versioned file)
+pub fn read_member(archive: &Path, member: &str) -> anyhow::Result<Vec<u8>> {
+    let mut target = normalize_member(Path::new(member));
+    for _ in 0..MAX_SYMLINK_DEPTH {
+        let mut link = None;
+        for entry in open_archive(archive)?.entries()? {
+            let mut entry = entry?;
+            if normalize_member(&entry.path()?) != target {
+                continue;
+            }
+
+            if entry.header().entry_type().is_symlink() {
+                link = entry.link_name()?.map(|l| l.into_owned());
+            } else {
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents)?;
+                return Ok(contents);
+            }
+            break;
+        }
+
+        match link {
+            Some(link) => {
+                // Relative links are relative to the directory containing the link
+                let base = target.parent().map(Path::to_path_buf).unwrap_or_default();
+                target = normalize_member(&base.join(link));
+            }
+            None => {
+                return Err(anyhow::Error::new(ArchiveError::MissingMember(
+                    archive.to_path_buf(),
+                    member.to_string(),
+                )));
+            }
+        }
+    }
+    Err(anyhow::Error::new(ArchiveError::SymlinkLoop(
+        archive.to_path_buf(),
+        member.to_string(),
+    )))
+}
+
+/// Read the contents of a file, which may be a member of an archive (see `split_archive_path`)
+pub fn read_binary(path: &Path) -> anyhow::Result<Vec<u8>> {
+    match split_archive_path(path) {
+        None => Ok(fs::read(path)?),
+        Some((archive, member)) => read_member(&archive, &member),
+    }
+}
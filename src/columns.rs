@@ -0,0 +1,162 @@
+use crate::demangle;
+use crate::options::{DemangleStyle, SymbolColumn};
+use crate::summarize::{
+    DynamicSymbolReference, ElfSummary, ExportedDynamicSymbol, VersionedSymbol,
+};
+
+const DEFAULT_REFERENCE_COLUMNS: [SymbolColumn; 4] = [
+    SymbolColumn::Type,
+    SymbolColumn::Binding,
+    SymbolColumn::Name,
+    SymbolColumn::Provider,
+];
+
+const DEFAULT_DEFINITION_COLUMNS: [SymbolColumn; 6] = [
+    SymbolColumn::Addr,
+    SymbolColumn::Size,
+    SymbolColumn::Type,
+    SymbolColumn::Binding,
+    SymbolColumn::Section,
+    SymbolColumn::Name,
+];
+
+/// The columns to show in tables of symbol references
+///
+/// These are the requested columns that make sense for references (which have
+/// no address, size, or section), or the defaults if no columns were requested
+pub fn reference_columns(spec: &[SymbolColumn]) -> Vec<SymbolColumn> {
+    if spec.is_empty() {
+        return DEFAULT_REFERENCE_COLUMNS.to_vec();
+    }
+    spec.iter()
+        .copied()
+        .filter(|c| {
+            !matches!(
+                c,
+                SymbolColumn::Addr | SymbolColumn::Size | SymbolColumn::Section
+            )
+        })
+        .collect()
+}
+
+/// The columns to show in tables of defined symbols
+pub fn definition_columns(spec: &[SymbolColumn]) -> Vec<SymbolColumn> {
+    if spec.is_empty() {
+        return DEFAULT_DEFINITION_COLUMNS.to_vec();
+    }
+    spec.iter()
+        .copied()
+        .filter(|c| *c != SymbolColumn::Provider)
+        .collect()
+}
+
+pub fn header(col: SymbolColumn) -> &'static str {
+    match col {
+        SymbolColumn::Addr => "Address",
+        SymbolColumn::Size => "Size",
+        SymbolColumn::Type => "Type",
+        SymbolColumn::Binding => "Binding",
+        SymbolColumn::Name => "Symbol",
+        SymbolColumn::Demangled => "Demangled",
+        SymbolColumn::Version => "Version",
+        SymbolColumn::Visibility => "Visibility",
+        SymbolColumn::Section => "Section",
+        SymbolColumn::Provider => "Provider",
+    }
+}
+
+pub fn headers(cols: &[SymbolColumn]) -> Vec<&'static str> {
+    cols.iter().map(|c| header(*c)).collect()
+}
+
+/// The name of the symbol, which includes the version unless it has its own column
+fn symbol_name(sym: &VersionedSymbol, cols: &[SymbolColumn]) -> String {
+    if cols.contains(&SymbolColumn::Version) {
+        sym.name.clone()
+    } else {
+        sym.to_string()
+    }
+}
+
+/// Render a size in bytes, either exactly or (if `human` is set) with a binary
+/// unit suffix (e.g., `1.2K` or `3.4M`) for quick comparison
+pub fn format_size(size: u64, human: bool) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+    if !human || size < 1024 {
+        return format!("{}", size);
+    }
+    let mut scaled = size as f64 / 1024.0;
+    let mut unit = 0;
+    while scaled >= 1024.0 && unit < UNITS.len() - 1 {
+        scaled /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", scaled, UNITS[unit])
+}
+
+/// Render the symbol hash tables of a binary (e.g., `GNU, SysV`), or `None`
+pub fn format_hash_tables(summary: &ElfSummary) -> String {
+    let tables = summary.hash_tables();
+    if tables.is_empty() {
+        return String::from("None");
+    }
+    tables
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn symbol_version(sym: &VersionedSymbol) -> String {
+    sym.version.clone().unwrap_or_default()
+}
+
+/// Render the cells of the given columns for a symbol reference
+///
+/// The `provider` is the rendering of the binary that provides the symbol (or
+/// the reason it is unresolved)
+pub fn reference_row(
+    sym_ref: &DynamicSymbolReference,
+    provider: &str,
+    cols: &[SymbolColumn],
+    demangle_style: DemangleStyle,
+) -> Vec<String> {
+    cols.iter()
+        .map(|col| match col {
+            SymbolColumn::Addr | SymbolColumn::Size | SymbolColumn::Section => String::new(),
+            SymbolColumn::Type => format!("{:?}", sym_ref.type_),
+            SymbolColumn::Binding => format!("{:?}", sym_ref.binding),
+            SymbolColumn::Name => symbol_name(&sym_ref.symbol, cols),
+            SymbolColumn::Demangled => demangle::demangle(&sym_ref.symbol.name, demangle_style),
+            SymbolColumn::Version => symbol_version(&sym_ref.symbol),
+            SymbolColumn::Visibility => format!("{:?}", sym_ref.visibility),
+            SymbolColumn::Provider => provider.to_string(),
+        })
+        .collect()
+}
+
+/// Render the cells of the given columns for a symbol defined by `summary`
+///
+/// Sizes are rendered with units if `human_sizes` is set (see `format_size`)
+pub fn definition_row(
+    summary: &ElfSummary,
+    sym_def: &ExportedDynamicSymbol,
+    cols: &[SymbolColumn],
+    human_sizes: bool,
+    demangle_style: DemangleStyle,
+) -> Vec<String> {
+    cols.iter()
+        .map(|col| match col {
+            SymbolColumn::Addr => summary.format_address(sym_def.address),
+            SymbolColumn::Size => format_size(sym_def.size, human_sizes),
+            SymbolColumn::Type => format!("{:?}", sym_def.type_),
+            SymbolColumn::Binding => format!("{:?}", sym_def.binding),
+            SymbolColumn::Name => symbol_name(&sym_def.symbol, cols),
+            SymbolColumn::Demangled => demangle::demangle(&sym_def.symbol.name, demangle_style),
+            SymbolColumn::Version => symbol_version(&sym_def.symbol),
+            SymbolColumn::Visibility => format!("{:?}", sym_def.visibility),
+            SymbolColumn::Section => summary.section_name(sym_def.section),
+            SymbolColumn::Provider => String::new(),
+        })
+        .collect()
+}
@@ -0,0 +1,234 @@
+use object::{Endian, Endianness};
+use serde::Serialize;
+use std::path::Path;
+
+/// A file mapped into the address space of the crashed process (from `NT_FILE`)
+#[derive(Clone, Debug, Serialize)]
+pub struct MappedFile {
+    pub start: u64,
+    pub end: u64,
+    /// The offset into the file of the start of the mapping, in bytes
+    pub file_offset: u64,
+    pub path: String,
+}
+
+/// The description of the crashed process recorded in the notes of a core dump
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CoreInfo {
+    /// The name of the executable (truncated to 15 characters by the kernel)
+    pub executable: Option<String>,
+    /// The command line of the process (truncated to 80 characters by the kernel)
+    pub arguments: Option<String>,
+    /// The signal that terminated the process
+    pub signal: Option<u16>,
+    pub mapped_files: Vec<MappedFile>,
+}
+
+impl CoreInfo {
+    /// The name of the terminating signal (using the Linux numbering shared by
+    /// x86 and ARM), if it is a common one
+    pub fn signal_name(&self) -> Option<&'static str> {
+        match self.signal? {
+            1 => Some("SIGHUP"),
+            2 => Some("SIGINT"),
+            3 => Some("SIGQUIT"),
+            4 => Some("SIGILL"),
+            5 => Some("SIGTRAP"),
+            6 => Some("SIGABRT"),
+            7 => Some("SIGBUS"),
+            8 => Some("SIGFPE"),
+            9 => Some("SIGKILL"),
+            11 => Some("SIGSEGV"),
+            13 => Some("SIGPIPE"),
+            15 => Some("SIGTERM"),
+            31 => Some("SIGSYS"),
+            _ => None,
+        }
+    }
+
+    /// The distinct shared libraries mapped into the process, in the order they were first mapped
+    pub fn shared_libraries(&self) -> Vec<&str> {
+        let mut libs: Vec<&str> = Vec::new();
+        for file in &self.mapped_files {
+            let is_library = Path::new(&file.path)
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().contains(".so"));
+            if is_library && !libs.contains(&file.path.as_str()) {
+                libs.push(&file.path);
+            }
+        }
+        libs
+    }
+}
+
+/// Read the `idx`th word (of `word_size` bytes) from `data`
+fn read_word(end: Endianness, data: &[u8], word_size: usize, idx: usize) -> Option<u64> {
+    let bytes = data.get(idx * word_size..(idx + 1) * word_size)?;
+    match word_size {
+        4 => Some(end.read_u32_bytes(bytes.try_into().ok()?).into()),
+        _ => Some(end.read_u64_bytes(bytes.try_into().ok()?)),
+    }
+}
+
+/// Read a NUL-padded string from a fixed-size field
+fn fixed_string(field: &[u8]) -> Option<String> {
+    let len = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    if len == 0 {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&field[..len]).into_owned())
+}
+
+/// Extract the executable name and arguments from an `NT_PRPSINFO` descriptor
+///
+/// The layout of the leading fields varies by architecture, but the structure
+/// always ends with `pr_fname[16]` followed by `pr_psargs[80]`
+pub fn prpsinfo_command(desc: &[u8]) -> (Option<String>, Option<String>) {
+    let len = desc.len();
+    if len < 96 {
+        return (None, None);
+    }
+    (
+        fixed_string(&desc[len - 96..len - 80]),
+        fixed_string(&desc[len - 80..]).map(|args| args.trim_end().to_string()),
+    )
+}
+
+/// Extract the current signal (`pr_cursig`) from an `NT_PRSTATUS` descriptor
+///
+/// This follows the three `int` fields of `pr_info`
+pub fn prstatus_signal(end: Endianness, desc: &[u8]) -> Option<u16> {
+    let bytes = desc.get(12..14)?;
+    Some(end.read_u16_bytes(bytes.try_into().ok()?))
+}
+
+/// Parse the mapped files from an `NT_FILE` descriptor
+///
+/// The layout (in words of the natural size of the binary) is:
+///
+/// 1. The number of mappings and the page size
+/// 2. A (start, end, offset in pages) triple for each mapping
+/// 3. The NUL-terminated path of each mapping, in the same order
+pub fn file_mappings(end: Endianness, desc: &[u8], word_size: usize) -> Option<Vec<MappedFile>> {
+    let count = read_word(end, desc, word_size, 0)? as usize;
+    let page_size = read_word(end, desc, word_size, 1)?;
+    let names_start = count
+        .checked_mul(3)?
+        .checked_add(2)?
+        .checked_mul(word_size)?;
+    let mut names = desc.get(names_start..)?.split(|b| *b == 0);
+
+    let mut res = Vec::new();
+    for idx in 0..count {
+        let entry = 2 + 3 * idx;
+        res.push(MappedFile {
+            start: read_word(end, desc, word_size, entry)?,
+            end: read_word(end, desc, word_size, entry + 1)?,
+            file_offset: read_word(end, desc, word_size, entry + 2)?.checked_mul(page_size)?,
+            path: String::from_utf8_lossy(names.next()?).into_owned(),
+        });
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `NT_FILE` descriptor with 64 bit little endian words
+    fn nt_file_64(page_size: u64, mappings: &[(u64, u64, u64, &str)]) -> Vec<u8> {
+        let mut desc = Vec::new();
+        desc.extend_from_slice(&(mappings.len() as u64).to_le_bytes());
+        desc.extend_from_slice(&page_size.to_le_bytes());
+        for (start, end, pages, _) in mappings {
+            for word in [start, end, pages] {
+                desc.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+        for (_, _, _, path) in mappings {
+            desc.extend_from_slice(path.as_bytes());
+            desc.push(0);
+        }
+        desc
+    }
+
+    #[test]
+    fn file_mappings_are_parsed() {
+        let desc = nt_file_64(
+            0x1000,
+            &[
+                (0x400000, 0x401000, 0, "/usr/bin/sleep"),
+                (0x7f0000000000, 0x7f0000028000, 2, "/usr/lib/libc.so.6"),
+            ],
+        );
+        let files = file_mappings(Endianness::Little, &desc, 8).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "/usr/bin/sleep");
+        assert_eq!((files[0].start, files[0].end), (0x400000, 0x401000));
+        assert_eq!(files[1].path, "/usr/lib/libc.so.6");
+        assert_eq!(files[1].file_offset, 0x2000);
+    }
+
+    #[test]
+    fn file_mappings_of_32_bit_big_endian_cores() {
+        let mut desc = Vec::new();
+        for word in [1u32, 0x1000, 0x10000, 0x12000, 3] {
+            desc.extend_from_slice(&word.to_be_bytes());
+        }
+        desc.extend_from_slice(b"/lib/libc.so.6\0");
+        let files = file_mappings(Endianness::Big, &desc, 4).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!((files[0].start, files[0].end), (0x10000, 0x12000));
+        assert_eq!(files[0].file_offset, 0x3000);
+        assert_eq!(files[0].path, "/lib/libc.so.6");
+    }
+
+    #[test]
+    fn malformed_file_mappings_are_rejected() {
+        let desc = nt_file_64(0x1000, &[(0x400000, 0x401000, 0, "/usr/bin/sleep")]);
+        // Truncated in the middle of a mapping
+        assert!(file_mappings(Endianness::Little, &desc[..30], 8).is_none());
+        // A count that cannot fit in the descriptor (or overflows)
+        let mut huge = desc.clone();
+        huge[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(file_mappings(Endianness::Little, &huge, 8).is_none());
+    }
+
+    /// An `NT_PRPSINFO` descriptor with `pr_fname` and `pr_psargs` after
+    /// `leading` bytes of other fields
+    fn prpsinfo(leading: usize, fname: &str, psargs: &str) -> Vec<u8> {
+        let mut desc = vec![0xaa; leading];
+        let mut field = |value: &str, size: usize| {
+            let mut bytes = value.as_bytes().to_vec();
+            bytes.resize(size, 0);
+            desc.extend_from_slice(&bytes);
+        };
+        field(fname, 16);
+        field(psargs, 80);
+        desc
+    }
+
+    #[test]
+    fn command_is_read_from_the_end_of_prpsinfo() {
+        // The x86_64 and i386 layouts, which differ in their leading fields
+        for leading in [40, 28] {
+            let desc = prpsinfo(leading, "sleep", "sleep 100 ");
+            assert_eq!(
+                prpsinfo_command(&desc),
+                (Some(String::from("sleep")), Some(String::from("sleep 100")))
+            );
+        }
+        assert_eq!(prpsinfo_command(&prpsinfo(40, "", "")), (None, None));
+        assert_eq!(prpsinfo_command(&[0; 95]), (None, None));
+    }
+
+    #[test]
+    fn signal_is_read_from_prstatus() {
+        let mut desc = vec![0; 112];
+        desc[12..14].copy_from_slice(&11u16.to_le_bytes());
+        assert_eq!(prstatus_signal(Endianness::Little, &desc), Some(11));
+        desc[12..14].copy_from_slice(&6u16.to_be_bytes());
+        assert_eq!(prstatus_signal(Endianness::Big, &desc), Some(6));
+        assert_eq!(prstatus_signal(Endianness::Little, &desc[..13]), None);
+    }
+}
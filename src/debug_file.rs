@@ -0,0 +1,135 @@
+//! Separate debug files for stripped binaries
+//!
+//! Distributions strip the static symbol tables from the binaries that they
+//! ship and provide them in separate debug files instead.  Like gdb, the debug
+//! file is found either by the build ID of the binary (as
+//! `.build-id/ab/cdef....debug` under a debug directory) or by the file name in
+//! its `.gnu_debuglink` section, which is looked for next to the binary, in the
+//! `.debug` subdirectory, and under each debug directory (at the path of the
+//! binary).  Files found through the debug link must match its CRC, since the
+//! name alone does not identify the build.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::summarize::{self, ElfSummary, SymbolSection};
+
+/// The CRC-32 (as used by `.gnu_debuglink`) of the bytes
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in bytes {
+        crc ^= u32::from(*b);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// The path of the debug file for a build ID under a debug directory
+fn build_id_path(debug_dir: &Path, build_id: &str) -> Option<PathBuf> {
+    if build_id.len() < 3 {
+        return None;
+    }
+    let (dir, file) = build_id.split_at(2);
+    Some(
+        debug_dir
+            .join(".build-id")
+            .join(dir)
+            .join(format!("{}.debug", file)),
+    )
+}
+
+/// The places to look for the file named by the debug link of `summary`
+fn debug_link_candidates(summary: &ElfSummary, file: &str, debug_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let binary_dir = summary.filename.parent().unwrap_or_else(|| Path::new(""));
+    let mut candidates = vec![binary_dir.join(file), binary_dir.join(".debug").join(file)];
+    for debug_dir in debug_dirs {
+        if let Ok(relative) = binary_dir.strip_prefix("/") {
+            candidates.push(debug_dir.join(relative).join(file));
+        }
+        candidates.push(debug_dir.join(file));
+    }
+    candidates
+}
+
+/// Find the separate debug file of `summary`, returning its path and contents
+fn find_debug_file(summary: &ElfSummary, debug_dirs: &[PathBuf]) -> Option<(PathBuf, Vec<u8>)> {
+    if let Some(build_id) = &summary.build_id {
+        for path in debug_dirs.iter().filter_map(|d| build_id_path(d, build_id)) {
+            if let Ok(bytes) = fs::read(&path) {
+                return Some((path, bytes));
+            }
+        }
+    }
+
+    let link = summary.debug_link.as_ref()?;
+    for path in debug_link_candidates(summary, &link.file, debug_dirs) {
+        // The debug file usually has a different name than the binary, but
+        // the binary itself would pass the CRC check if it is not stripped
+        if path == summary.filename {
+            continue;
+        }
+        let bytes = match fs::read(&path) {
+            Err(_) => continue,
+            Ok(bytes) => bytes,
+        };
+        if crc32(&bytes) == link.crc {
+            return Some((path, bytes));
+        }
+        log::debug!(
+            "{}: rejected debug file {}: the CRC does not match the debug link",
+            summary.filename.to_string_lossy(),
+            path.to_string_lossy()
+        );
+    }
+    None
+}
+
+/// Read the symbols of a stripped binary from its separate debug file, if one
+/// can be found in the `debug_dirs` (or next to the binary)
+///
+/// Returns false if there is no debug file
+pub fn merge_debug_symbols(
+    summary: &mut ElfSummary,
+    debug_dirs: &[PathBuf],
+) -> anyhow::Result<bool> {
+    let (path, bytes) = match find_debug_file(summary, debug_dirs) {
+        None => return Ok(false),
+        Some(found) => found,
+    };
+    let (symbols, sections) = summarize::symtab_definitions(&path, &bytes)?;
+    summary.debug_symbols = symbols
+        .into_iter()
+        .map(|mut sym| {
+            if let SymbolSection::Section(idx) = sym.section {
+                sym.section = SymbolSection::DebugFile(idx);
+            }
+            sym
+        })
+        .collect();
+    summary.debug_sections = sections;
+    summary.debug_file = Some(path);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_the_standard_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn build_id_paths_split_off_the_first_byte() {
+        assert_eq!(
+            build_id_path(Path::new("/usr/lib/debug"), "abcdef01"),
+            Some(PathBuf::from("/usr/lib/debug/.build-id/ab/cdef01.debug"))
+        );
+        assert_eq!(build_id_path(Path::new("/usr/lib/debug"), "ab"), None);
+    }
+}
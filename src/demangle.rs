@@ -0,0 +1,51 @@
+use cpp_demangle::{DemangleOptions, Symbol};
+
+use crate::options::DemangleStyle;
+
+/// Returns true if the name is a legacy Rust mangled name
+///
+/// Legacy Rust names use the C++ `_ZN...E` encoding, but the last path
+/// component is always a hash of the form `17h` followed by 16 hex digits
+fn is_rust_legacy(name: &str) -> bool {
+    let mangled = match name.strip_prefix("_ZN").and_then(|n| n.strip_suffix('E')) {
+        None => return false,
+        Some(mangled) => mangled,
+    };
+    match mangled.len().checked_sub(19) {
+        None => false,
+        Some(idx) => mangled.get(idx..).is_some_and(|hash| {
+            hash.starts_with("17h") && hash[3..].chars().all(|c| c.is_ascii_hexdigit())
+        }),
+    }
+}
+
+fn demangle_rust(name: &str, style: DemangleStyle) -> Option<String> {
+    let sym = rustc_demangle::try_demangle(name).ok()?;
+    match style {
+        DemangleStyle::Full => Some(format!("{}", sym)),
+        // The alternate format omits the hash
+        DemangleStyle::Short => Some(format!("{:#}", sym)),
+    }
+}
+
+fn demangle_cpp(name: &str, style: DemangleStyle) -> Option<String> {
+    let opts = match style {
+        DemangleStyle::Full => DemangleOptions::default(),
+        DemangleStyle::Short => DemangleOptions::default().no_params().no_return_type(),
+    };
+    Symbol::new(name).ok()?.demangle(&opts).ok()
+}
+
+/// Demangle a Rust (v0 or legacy) or C++ symbol name, returning the original
+/// name if it is not mangled (or cannot be demangled)
+///
+/// Rust is tried first because legacy Rust names are also valid C++ names,
+/// which would demangle with the hash as the last path component
+pub fn demangle(name: &str, style: DemangleStyle) -> String {
+    let demangled = if name.starts_with("_R") || is_rust_legacy(name) {
+        demangle_rust(name, style).or_else(|| demangle_cpp(name, style))
+    } else {
+        demangle_cpp(name, style)
+    };
+    demangled.unwrap_or_else(|| name.to_string())
+}
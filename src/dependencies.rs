@@ -1,53 +1,290 @@
+use serde::Serialize;
 use std::collections;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::summarize::{summarize_path, BinaryType, ElfSummary};
+use crate::ld_cache::LdCache;
+use crate::resolve_symbols::{diagnose_unresolved, resolve_symbols};
+use crate::search_path::{self, SearchDir, SearchPathSource};
+use crate::summarize::{summarize_path, BinaryType, ElfSummary, SymbolBinding, VersionedSymbol};
 
 #[derive(thiserror::Error, Debug)]
 pub enum SearchError {
-    #[error("Could not find dependency {0}")]
-    MissingLibraryDependency(String),
+    #[error("Could not find dependency {0} (searched {})", display_dirs(.1))]
+    MissingLibraryDependency(String, Vec<PathBuf>),
 }
 
+fn display_dirs(dirs: &[PathBuf]) -> String {
+    if dirs.is_empty() {
+        return String::from("no directories");
+    }
+    dirs.iter()
+        .map(|d| d.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A library that was found on the search path, but skipped because its ELF
+/// class or byte order differs from the root binary (as the loader would skip it)
+#[derive(Clone, Debug, Serialize)]
+pub struct IncompatibleCandidate {
+    /// The name of the dependency being searched for
+    pub name: String,
+    pub path: PathBuf,
+    pub bit_size: usize,
+    pub endianness: String,
+}
+
+impl std::fmt::Display for IncompatibleCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Skipped {} for {}: it is a {} bit {} endian library",
+            self.path.to_string_lossy(),
+            self.name,
+            self.bit_size,
+            self.endianness
+        )
+    }
+}
+
+/// The version suffix of `file_name` if it is a more specific version of
+/// `lib_name` (e.g., `[1, 2, 3]` for `libfoo.so.1.2.3` when looking for `libfoo.so.1`)
+fn version_suffix(lib_name: &str, file_name: &str) -> Option<Vec<u64>> {
+    let suffix = file_name.strip_prefix(lib_name)?.strip_prefix('.')?;
+    suffix.split('.').map(|c| c.parse::<u64>().ok()).collect()
+}
+
+/// The files in `dir` that could provide `lib_name`, in order of preference
+///
+/// This is the file with exactly that name if it exists.  Otherwise (e.g., if
+/// the `libfoo.so.1` symlink was not installed), it is every minor/patch
+/// version of the library (e.g., `libfoo.so.1.2.3`), highest first, so that a
+/// newer version that cannot be loaded falls back to an older one.  If neither
+/// exists, this is the exact path, so that the failure is reported in terms of it.
+fn library_candidates(dir: &Path, lib_name: &str) -> Vec<PathBuf> {
+    let exact = dir.join(lib_name);
+    if exact.exists() {
+        return vec![exact];
+    }
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![exact],
+    };
+    let mut versions: Vec<(Vec<u64>, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let file_name = e.file_name();
+            let version = version_suffix(lib_name, file_name.to_str()?)?;
+            Some((version, e.path()))
+        })
+        .collect();
+    if versions.is_empty() {
+        return vec![exact];
+    }
+    versions.sort_by(|a, b| b.cmp(a));
+    versions.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Find the named library on the search path, returning its summary and the
+/// source of the directory it was found in
+///
+/// Libraries with a different ELF class or byte order than `root` cannot be
+/// loaded into it, so they are skipped and recorded in `incompatible`
 fn analyze_one_dependency(
-    search_path: &Vec<PathBuf>,
+    search_path: &[SearchDir],
+    root: &ElfSummary,
     lib_name: &str,
-) -> anyhow::Result<ElfSummary> {
-    for dir in search_path {
-        let candidate = dir.join(PathBuf::from(lib_name));
-        match summarize_path(&candidate) {
-            Err(_) => {}
-            Ok(summ) => {
-                return Ok(summ);
+    incompatible: &mut Vec<IncompatibleCandidate>,
+) -> Result<(ElfSummary, SearchPathSource), SearchError> {
+    for search_dir in search_path {
+        for candidate in library_candidates(&search_dir.dir, lib_name) {
+            match summarize_path(&candidate) {
+                Err(err) => {
+                    log::debug!(
+                        "{}: rejected {} ({}): {}",
+                        lib_name,
+                        candidate.to_string_lossy(),
+                        search_dir.source,
+                        err
+                    );
+                }
+                Ok(summ)
+                    if summ.bit_size != root.bit_size || summ.endianness != root.endianness =>
+                {
+                    log::debug!(
+                        "{}: rejected {} ({}): incompatible {} bit {:?} binary",
+                        lib_name,
+                        candidate.to_string_lossy(),
+                        search_dir.source,
+                        summ.bit_size,
+                        summ.endianness
+                    );
+                    incompatible.push(IncompatibleCandidate {
+                        name: lib_name.to_string(),
+                        path: candidate,
+                        bit_size: summ.bit_size,
+                        endianness: format!("{:?}", summ.endianness).to_lowercase(),
+                    });
+                }
+                Ok(summ) => {
+                    log::debug!(
+                        "{}: selected {} ({})",
+                        lib_name,
+                        candidate.to_string_lossy(),
+                        search_dir.source
+                    );
+                    return Ok((summ, search_dir.source));
+                }
             }
         }
     }
-    Err(anyhow::Error::new(SearchError::MissingLibraryDependency(
+    Err(SearchError::MissingLibraryDependency(
         lib_name.to_string(),
-    )))
+        search_path.iter().map(|d| d.dir.clone()).collect(),
+    ))
+}
+
+/// A dependency that resolved to a file outside of every sysroot (e.g.,
+/// through a host directory in `LD_LIBRARY_PATH`)
+pub struct OutsideSysroot {
+    pub name: String,
+    pub path: PathBuf,
+    pub source: Option<SearchPathSource>,
 }
 
-struct WorkQueue {
-    work_items: collections::VecDeque<String>,
+impl std::fmt::Display for OutsideSysroot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} resolved to {}, which is outside of the sysroot",
+            self.name,
+            self.path.to_string_lossy()
+        )?;
+        if let Some(source) = self.source {
+            write!(f, " (found through {})", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// The dependencies in `resolution` that were found outside of all of the `sysroots`
+///
+/// This is almost always a mistake when analyzing a binary for another system.
+/// There is nothing to check if one of the sysroots is the host root (`/`).
+pub fn outside_sysroots(resolution: &Resolution, sysroots: &[PathBuf]) -> Vec<OutsideSysroot> {
+    if sysroots.iter().any(|root| root == Path::new("/")) {
+        return Vec::new();
+    }
+    resolution
+        .dependencies
+        .iter()
+        .filter_map(|(name, dep)| dep.as_ref().map(|d| (name, &d.filename)))
+        .filter(|(_, path)| !sysroots.iter().any(|root| path.starts_with(root)))
+        .map(|(name, path)| OutsideSysroot {
+            name: name.clone(),
+            path: path.clone(),
+            source: resolution.sources.get(name).copied(),
+        })
+        .collect()
+}
+
+/// The results of resolving the transitive dependencies of a binary
+pub struct Resolution {
+    /// Each library in the closure, mapped to its summary (or `None` if it could not be found)
+    pub dependencies: collections::BTreeMap<String, Option<ElfSummary>>,
+    /// The reason each unresolved library could not be found
+    pub failures: collections::BTreeMap<String, SearchError>,
+    /// The source of the search path entry that each resolved library was found through
+    pub sources: collections::BTreeMap<String, SearchPathSource>,
+    /// Libraries that were found outside of the default system directories,
+    /// but left out of the closure because only system libraries were requested
+    pub excluded: collections::BTreeMap<String, PathBuf>,
+    /// Libraries that were needed, but not searched for because they were
+    /// explicitly ignored (e.g., because the host provides them)
+    pub ignored: collections::BTreeSet<String>,
+    /// Libraries that were needed, but not searched for because they are
+    /// beyond the maximum depth of the search
+    pub depth_limited: collections::BTreeSet<String>,
+    /// Direct dependencies of the root binary that are also needed by another
+    /// library in the closure, so the closure is not a simple tree
+    pub also_indirect: collections::BTreeSet<String>,
+    /// Libraries that were skipped because they do not match the ELF class or
+    /// byte order of the root binary (e.g., due to a misconfigured sysroot)
+    pub incompatible: Vec<IncompatibleCandidate>,
+    /// Every library in the closure (including the unresolved and excluded
+    /// ones), in the breadth-first order it was discovered in; this is the
+    /// order the loader searches for symbol definitions
+    pub order: Vec<String>,
+}
+
+/// A library to search for
+struct WorkItem {
+    name: String,
+    /// The depth of the library in the dependency tree
+    depth: usize,
+    /// The name of the library that needs it (or `None` for the root binary)
+    requester: Option<String>,
+}
+
+struct WorkQueue<'a> {
+    /// The names to search for
+    work_items: collections::VecDeque<WorkItem>,
     seen_items: collections::HashSet<String>,
+    /// The names that are never enqueued
+    ignored_names: &'a [String],
+    /// The ignored names that were encountered
+    ignored: collections::BTreeSet<String>,
+    /// Dependencies are not enqueued beyond this depth (if any)
+    max_depth: Option<usize>,
+    /// The names that were not enqueued because they are too deep
+    depth_limited: collections::BTreeSet<String>,
+    /// The names needed by a library (rather than by the root binary)
+    needed_indirectly: collections::BTreeSet<String>,
+    /// The names in the order they were taken from the queue
+    order: Vec<String>,
 }
 
-impl WorkQueue {
-    fn new() -> Self {
+impl<'a> WorkQueue<'a> {
+    fn new(ignored_names: &'a [String], max_depth: Option<usize>) -> Self {
         WorkQueue {
             work_items: collections::VecDeque::new(),
             seen_items: collections::HashSet::new(),
+            ignored_names,
+            ignored: collections::BTreeSet::new(),
+            max_depth,
+            depth_limited: collections::BTreeSet::new(),
+            needed_indirectly: collections::BTreeSet::new(),
+            order: Vec::new(),
         }
     }
 
-    fn add_dependencies(&mut self, summ: &ElfSummary) {
+    /// Enqueue the dependencies of `summ`, which is at `depth` in the
+    /// dependency tree (the root binary is at depth zero, with no `name`)
+    fn add_dependencies(&mut self, summ: &ElfSummary, name: Option<&str>, depth: usize) {
         match &summ.binary_type {
             BinaryType::Static => {}
             BinaryType::Dynamic(dyn_deps) => {
                 for dep in &dyn_deps.deps {
+                    if self.ignored_names.contains(dep) {
+                        self.ignored.insert(dep.to_string());
+                        continue;
+                    }
+                    if name.is_some() {
+                        self.needed_indirectly.insert(dep.to_string());
+                    }
                     match self.seen_items.get(dep.as_str()) {
+                        None if self.max_depth.is_some_and(|max| depth >= max) => {
+                            // Since the search is breadth-first, a library
+                            // that is too deep here cannot be reached from
+                            // a shallower one later
+                            self.depth_limited.insert(dep.to_string());
+                        }
                         None => {
-                            self.work_items.push_back(dep.to_string());
+                            self.work_items.push_back(WorkItem {
+                                name: dep.to_string(),
+                                depth: depth + 1,
+                                requester: name.map(String::from),
+                            });
                             self.seen_items.insert(dep.to_string());
                         }
                         Some(_) => {}
@@ -57,35 +294,505 @@ impl WorkQueue {
         }
     }
 
-    fn take_work(&mut self) -> Option<String> {
-        self.work_items.pop_front()
+    fn take_work(&mut self) -> Option<WorkItem> {
+        let item = self.work_items.pop_front()?;
+        self.order.push(item.name.clone());
+        Some(item)
     }
 }
 
 /// Recursively search for dependencies on the search path
 ///
-/// The Elf summaries will not include the input binary
+/// Each library is searched for on the search path of the module that needs
+/// it, under each of the `sysroots`, falling back to the `ld_cache` (see Note
+/// [Search Path]).  Libraries found through the cache count as system libraries.
+///
+/// The Elf summaries will not include the input binary.  If `system_only` is
+/// set, libraries found anywhere other than the default system directories
+/// are excluded (and their dependencies are not followed).  Libraries named
+/// in `ignored` are not searched for at all (and are not reported as unresolved).
+/// If there is a `max_depth`, libraries further than that from the input
+/// binary are not searched for either (the direct dependencies are at depth 1).
 pub fn resolve_dependencies(
-    search_path: &Vec<PathBuf>,
+    sysroots: &[PathBuf],
+    ld_cache: &LdCache,
     summ: &ElfSummary,
-) -> collections::BTreeMap<String, Option<ElfSummary>> {
-    let mut res = collections::BTreeMap::new();
-    let mut queue = WorkQueue::new();
+    system_only: bool,
+    ignored: &[String],
+    max_depth: Option<usize>,
+) -> Resolution {
+    let mut res: collections::BTreeMap<String, Option<ElfSummary>> = collections::BTreeMap::new();
+    let mut failures = collections::BTreeMap::new();
+    let mut sources = collections::BTreeMap::new();
+    let mut excluded = collections::BTreeMap::new();
+    let mut incompatible = Vec::new();
+    // The library that each resolved library was first needed by (`None` for
+    // the root binary)
+    let mut loaded_by: collections::HashMap<String, Option<String>> = collections::HashMap::new();
+    let mut queue = WorkQueue::new(ignored, max_depth);
 
-    queue.add_dependencies(summ);
+    queue.add_dependencies(summ, None, 0);
 
-    while let Some(dep_name) = queue.take_work() {
-        match analyze_one_dependency(search_path, dep_name.as_str()) {
-            Err(_) => {
+    while let Some(item) = queue.take_work() {
+        let dep_name = item.name;
+        // The chain of modules that led to this library, from the one that
+        // needs it back to the root; only resolved libraries have their
+        // dependencies enqueued, so every link is in `res`
+        let mut loaders: Vec<&ElfSummary> = Vec::new();
+        let mut next = item.requester.as_ref();
+        while let Some(name) = next {
+            if let Some(Some(dep)) = res.get(name) {
+                loaders.push(dep);
+            }
+            next = loaded_by.get(name).and_then(|r| r.as_ref());
+        }
+        loaders.push(summ);
+        let search_path = search_path::search_path(sysroots, ld_cache, &loaders, &dep_name);
+        match analyze_one_dependency(&search_path, summ, dep_name.as_str(), &mut incompatible) {
+            Err(err) => {
                 // Report this as a failed lookup
-                res.insert(dep_name, None);
+                res.insert(dep_name.clone(), None);
+                failures.insert(dep_name, err);
             }
-            Ok(dep_summary) => {
-                queue.add_dependencies(&dep_summary);
+            Ok((dep_summary, source)) => {
+                let is_system = matches!(
+                    source,
+                    SearchPathSource::Default | SearchPathSource::LdCache
+                );
+                if system_only && !is_system {
+                    log::debug!(
+                        "{}: excluded {}, since it is not in a system directory",
+                        dep_name,
+                        dep_summary.filename.to_string_lossy()
+                    );
+                    excluded.insert(dep_name, dep_summary.filename);
+                    continue;
+                }
+                queue.add_dependencies(&dep_summary, Some(&dep_name), item.depth);
+                loaded_by.insert(dep_name.clone(), item.requester);
+                sources.insert(dep_name.clone(), source);
                 res.insert(dep_name, Some(dep_summary));
             }
         }
     }
 
+    log::debug!(
+        "{}: dependencies in resolution order: {}",
+        summ.filename.to_string_lossy(),
+        queue.order.join(", ")
+    );
+
+    let also_indirect = match &summ.binary_type {
+        BinaryType::Static => collections::BTreeSet::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data
+            .deps
+            .iter()
+            .filter(|d| queue.needed_indirectly.contains(d.as_str()))
+            .cloned()
+            .collect(),
+    };
+
+    Resolution {
+        dependencies: res,
+        failures,
+        sources,
+        excluded,
+        ignored: queue.ignored,
+        depth_limited: queue.depth_limited,
+        also_indirect,
+        incompatible,
+        order: queue.order,
+    }
+}
+
+/// Find the modules in the closure that directly depend on the named library
+///
+/// This is the inverse of the edges recorded in `DynamicData::deps`; the root
+/// binary is considered along with all of the resolved dependencies
+pub fn needed_by<'a>(
+    root: &'a ElfSummary,
+    deps: &'a collections::BTreeMap<String, Option<ElfSummary>>,
+    lib_name: &str,
+) -> Vec<&'a ElfSummary> {
+    std::iter::once(root)
+        .chain(deps.values().filter_map(|d| d.as_ref()))
+        .filter(|summ| match &summ.binary_type {
+            BinaryType::Static => false,
+            BinaryType::Dynamic(dyn_data) => dyn_data.deps.iter().any(|d| d == lib_name),
+        })
+        .collect()
+}
+
+/// The symbol versions that a binary requires from one of its direct
+/// dependencies, compared with the versions that the dependency provides
+#[derive(Serialize)]
+pub struct VersionCompatibility {
+    pub library: String,
+    /// The path the dependency resolved to, or `None` if it could not be found
+    pub path: Option<PathBuf>,
+    /// The versions required from the dependency (from `.gnu.version_r`)
+    pub required: Vec<String>,
+    /// The versions the dependency defines (from `.gnu.version_d`)
+    pub provided: Vec<String>,
+    /// The required versions that the dependency does not provide
+    pub missing: Vec<String>,
+}
+
+/// Compare the symbol versions required by `root` with those provided by each of its direct dependencies
+pub fn version_compatibility(
+    root: &ElfSummary,
+    deps: &collections::BTreeMap<String, Option<ElfSummary>>,
+) -> Vec<VersionCompatibility> {
+    let dyn_data = match &root.binary_type {
+        BinaryType::Static => return Vec::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data,
+    };
+
+    let mut res = Vec::new();
+    for lib_name in &dyn_data.deps {
+        let required: Vec<String> = dyn_data
+            .version_requirements
+            .iter()
+            .filter(|req| &req.library == lib_name)
+            .flat_map(|req| req.versions.iter().cloned())
+            .collect();
+        let dep = deps.get(lib_name).and_then(|d| d.as_ref());
+        let provided = match dep.map(|d| &d.binary_type) {
+            Some(BinaryType::Dynamic(dep_data)) => dep_data.version_definitions.clone(),
+            _ => Vec::new(),
+        };
+        let missing = required
+            .iter()
+            .filter(|v| !provided.contains(*v))
+            .cloned()
+            .collect();
+        res.push(VersionCompatibility {
+            library: lib_name.clone(),
+            path: dep.map(|d| d.filename.clone()),
+            required,
+            provided,
+            missing,
+        });
+    }
+    res
+}
+
+/// The number of the root binary's symbol references that are satisfied by
+/// one of its direct dependencies
+#[derive(Serialize)]
+pub struct DependencyUsage {
+    pub library: String,
+    /// The path the dependency resolved to, or `None` if it could not be found
+    pub path: Option<PathBuf>,
+    pub satisfied: usize,
+}
+
+/// Count the symbol references of `root` satisfied by each of its direct
+/// dependencies, with the most used dependency first
+///
+/// Dependencies that satisfy no references are included (with a count of
+/// zero), since they are candidates for removal.  References satisfied by
+/// indirect dependencies are not counted against any direct dependency.
+pub fn dependency_usage(
+    root: &ElfSummary,
+    deps: &collections::BTreeMap<String, Option<ElfSummary>>,
+    resolutions: &collections::BTreeMap<VersionedSymbol, &ElfSummary>,
+) -> Vec<DependencyUsage> {
+    let dyn_data = match &root.binary_type {
+        BinaryType::Static => return Vec::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data,
+    };
+
+    let mut counts: collections::BTreeMap<&Path, usize> = collections::BTreeMap::new();
+    for provider in resolutions.values() {
+        *counts.entry(provider.filename.as_path()).or_insert(0) += 1;
+    }
+
+    let mut res: Vec<DependencyUsage> = dyn_data
+        .deps
+        .iter()
+        .map(|lib_name| {
+            let path = deps
+                .get(lib_name)
+                .and_then(|d| d.as_ref())
+                .map(|d| d.filename.clone());
+            let satisfied = path
+                .as_ref()
+                .and_then(|p| counts.get(p.as_path()))
+                .copied()
+                .unwrap_or(0);
+            DependencyUsage {
+                library: lib_name.clone(),
+                path,
+                satisfied,
+            }
+        })
+        .collect();
+    // The sort is stable, so ties stay in DT_NEEDED order
+    res.sort_by_key(|dep| std::cmp::Reverse(dep.satisfied));
+    res
+}
+
+/// A binary in the dependency closure with its size on disk
+#[derive(Serialize)]
+pub struct FileSize {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// The combined size on disk of a binary and its dependency closure
+#[derive(Serialize)]
+pub struct ClosureSize {
+    pub root: PathBuf,
+    pub total: u64,
+    /// Each file in the closure (including the root), largest first
+    pub files: Vec<FileSize>,
+}
+
+/// Sum the sizes of `root` and each resolved library in its closure
+///
+/// Libraries reached through more than one name are only counted once.  Files
+/// whose size cannot be determined (e.g., members of archives) are left out
+/// with a warning.
+pub fn closure_size(
+    root: &ElfSummary,
+    deps: &collections::BTreeMap<String, Option<ElfSummary>>,
+) -> ClosureSize {
+    let paths: collections::BTreeSet<&PathBuf> = std::iter::once(root)
+        .chain(deps.values().filter_map(|d| d.as_ref()))
+        .map(|summ| &summ.filename)
+        .collect();
+    let mut files: Vec<FileSize> = paths
+        .into_iter()
+        .filter_map(|path| match std::fs::metadata(path) {
+            Ok(meta) => Some(FileSize {
+                path: path.clone(),
+                size: meta.len(),
+            }),
+            Err(err) => {
+                log::warn!(
+                    "Could not determine the size of {}: {}",
+                    path.to_string_lossy(),
+                    err
+                );
+                None
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    ClosureSize {
+        root: root.filename.clone(),
+        total: files.iter().map(|f| f.size).sum(),
+        files,
+    }
+}
+
+/// The kinds of failure that prevent a binary from loading
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnresolvedKind {
+    /// A library that could not be found on the search path
+    Dependency,
+    /// A symbol reference that no module in the closure defines (with the required version)
+    Symbol,
+}
+
+impl std::fmt::Display for UnresolvedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnresolvedKind::Dependency => write!(f, "dependency"),
+            UnresolvedKind::Symbol => write!(f, "symbol"),
+        }
+    }
+}
+
+/// A dependency or symbol reference that could not be resolved
+#[derive(Serialize)]
+pub struct UnresolvedItem {
+    pub kind: UnresolvedKind,
+    /// The library name or (versioned) symbol
+    pub name: String,
+    /// The module in the closure that requires it
+    pub needed_by: PathBuf,
+    pub reason: String,
+}
+
+/// Collect every unresolved dependency and symbol reference in the closure of `root`
+///
+/// The references of each module are resolved against every module in the
+/// closure (including `root`), as the loader uses a single global scope.  Weak
+/// references are not included, since the loader allows them to be unresolved.
+pub fn unresolved_items(root: &ElfSummary, resolution: &Resolution) -> Vec<UnresolvedItem> {
+    let modules: Vec<&ElfSummary> = std::iter::once(root)
+        .chain(resolution.dependencies.values().filter_map(|d| d.as_ref()))
+        .collect();
+
+    let mut res = Vec::new();
+    for (name, err) in &resolution.failures {
+        for dependent in needed_by(root, &resolution.dependencies, name) {
+            res.push(UnresolvedItem {
+                kind: UnresolvedKind::Dependency,
+                name: name.clone(),
+                needed_by: dependent.filename.clone(),
+                reason: err.to_string(),
+            });
+        }
+    }
+
+    for module in &modules {
+        let refs = match &module.binary_type {
+            BinaryType::Static => continue,
+            BinaryType::Dynamic(dyn_data) => &dyn_data.dynamic_symbol_refs,
+        };
+        let resolutions = resolve_symbols(refs, &modules);
+        let unresolved = diagnose_unresolved(refs, &modules, &resolutions);
+        for sym_ref in refs {
+            if matches!(sym_ref.binding, SymbolBinding::Weak) {
+                continue;
+            }
+            if let Some(reason) = unresolved.get(&sym_ref.symbol) {
+                res.push(UnresolvedItem {
+                    kind: UnresolvedKind::Symbol,
+                    name: sym_ref.symbol.to_string(),
+                    needed_by: module.filename.clone(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+    }
     res
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty directory for a test
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("binary-walkr-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn versioned_file_matches_without_symlink() {
+        let dir = test_dir("versioned");
+        for name in [
+            "libfoo.so.1.2.3",
+            "libfoo.so.1.10.0",
+            "libfoo.so.10.0",
+            "libfoo.so.1.x",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        assert_eq!(
+            library_candidates(&dir, "libfoo.so.1"),
+            vec![dir.join("libfoo.so.1.10.0"), dir.join("libfoo.so.1.2.3")]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn exact_file_is_preferred() {
+        let dir = test_dir("exact");
+        for name in ["libfoo.so.1", "libfoo.so.1.2.3"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        assert_eq!(
+            library_candidates(&dir, "libfoo.so.1"),
+            vec![dir.join("libfoo.so.1")]
+        );
+        assert_eq!(
+            library_candidates(&dir, "libbar.so.2"),
+            vec![dir.join("libbar.so.2")]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unloadable_version_falls_back_to_an_older_one() {
+        let dir = test_dir("fallback");
+        std::fs::write(dir.join("libfixture.so.1.10.0"), b"not an ELF file").unwrap();
+        std::fs::copy(
+            "tests/fixtures/libfixture.so",
+            dir.join("libfixture.so.1.2.3"),
+        )
+        .unwrap();
+        let root = summarize_path(&PathBuf::from("tests/fixtures/dynamic")).unwrap();
+        let search_path = [SearchDir {
+            dir: dir.clone(),
+            source: SearchPathSource::Default,
+        }];
+        let mut incompatible = Vec::new();
+        let (summary, source) =
+            analyze_one_dependency(&search_path, &root, "libfixture.so.1", &mut incompatible)
+                .unwrap();
+        assert_eq!(summary.filename, dir.join("libfixture.so.1.2.3"));
+        assert_eq!(source, SearchPathSource::Default);
+        assert!(incompatible.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn origin_is_relative_to_the_library_that_needs_it() {
+        // The executable finds libfixture.so through its own `$ORIGIN/plugins`, and
+        // libfixture.so finds libinner.so through `$ORIGIN/../lib`, which only
+        // exists relative to the plugins directory
+        let fixtures = PathBuf::from("tests/fixtures/origin");
+        let summary = summarize_path(&fixtures.join("app")).unwrap();
+        let resolution = resolve_dependencies(
+            &[PathBuf::from("/")],
+            &LdCache::default(),
+            &summary,
+            false,
+            &[],
+            None,
+        );
+        assert!(resolution.failures.is_empty());
+        let path_of = |name: &str| {
+            resolution.dependencies[name]
+                .as_ref()
+                .map(|dep| dep.filename.clone())
+        };
+        assert_eq!(
+            path_of("libfixture.so"),
+            Some(fixtures.join("plugins/libfixture.so"))
+        );
+        assert_eq!(
+            path_of("libinner.so"),
+            Some(fixtures.join("plugins/../lib/libinner.so"))
+        );
+        assert_eq!(resolution.sources["libinner.so"], SearchPathSource::Runpath);
+    }
+
+    #[test]
+    fn dependencies_outside_the_sysroot_are_reported() {
+        let fixtures = PathBuf::from("tests/fixtures");
+        let summary = summarize_path(&fixtures.join("dynamic")).unwrap();
+        let resolution = resolve_dependencies(
+            &[PathBuf::from("/")],
+            &LdCache::default(),
+            &summary,
+            false,
+            &[],
+            None,
+        );
+        assert!(resolution.failures.is_empty());
+
+        // Everything is inside of the root filesystem
+        assert!(outside_sysroots(&resolution, &[PathBuf::from("/")]).is_empty());
+        assert!(outside_sysroots(&resolution, std::slice::from_ref(&fixtures)).is_empty());
+
+        // libfixture.so is found through the `$ORIGIN` RPATH of the binary,
+        // which is not under the sysroot
+        let sysroot = test_dir("sysroot");
+        let outside = outside_sysroots(&resolution, std::slice::from_ref(&sysroot));
+        assert_eq!(outside.len(), 1);
+        assert_eq!(outside[0].name, "libfixture.so");
+        assert_eq!(outside[0].path, fixtures.join("libfixture.so"));
+        assert_eq!(outside[0].source, Some(SearchPathSource::Rpath));
+        std::fs::remove_dir_all(&sysroot).unwrap();
+    }
+}
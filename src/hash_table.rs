@@ -0,0 +1,55 @@
+use object::{Endian, Endianness};
+
+/// Read the `idx`th 32 bit word from `data`
+fn read_word(end: Endianness, data: &[u8], idx: usize) -> Option<u32> {
+    let bytes = data.get(idx * 4..idx * 4 + 4)?;
+    Some(end.read_u32_bytes(bytes.try_into().ok()?))
+}
+
+/// The number of dynamic symbols covered by a System V hash table (`.hash` / `DT_HASH`)
+///
+/// The table starts with the number of buckets and then the number of chain
+/// entries, which is exactly the number of symbols in the dynamic symbol table
+pub fn sysv_symbol_count(end: Endianness, data: &[u8]) -> Option<usize> {
+    read_word(end, data, 1).map(|nchain| nchain as usize)
+}
+
+/// The number of dynamic symbols covered by a GNU hash table (`.gnu.hash` / `DT_GNU_HASH`)
+///
+/// The GNU hash table does not record the number of symbols directly.  The
+/// layout is:
+///
+/// 1. A header of four words: `nbuckets`, `symoffset`, `bloom_size`, and `bloom_shift`
+/// 2. `bloom_size` bloom filter words (which are the natural word size of the binary)
+/// 3. `nbuckets` buckets, each holding the lowest symbol index in its chain
+/// 4. The chain values, one per hashed symbol (i.e., starting at `symoffset`)
+///
+/// The last chain ends at an entry with the low bit set, so the number of
+/// symbols is found by walking the chain of the highest bucket to its end.
+/// Symbols below `symoffset` are not hashed, but still count.
+pub fn gnu_symbol_count(end: Endianness, data: &[u8], word_size: usize) -> Option<usize> {
+    let nbuckets = read_word(end, data, 0)? as usize;
+    let symoffset = read_word(end, data, 1)? as usize;
+    let bloom_size = read_word(end, data, 2)? as usize;
+    let buckets_start = 4 + bloom_size * (word_size / 4);
+    let chains_start = buckets_start + nbuckets;
+
+    let mut max_sym = 0;
+    for bucket in 0..nbuckets {
+        max_sym = std::cmp::max(
+            max_sym,
+            read_word(end, data, buckets_start + bucket)? as usize,
+        );
+    }
+    if max_sym < symoffset {
+        return Some(symoffset);
+    }
+
+    loop {
+        let chain_value = read_word(end, data, chains_start + (max_sym - symoffset))?;
+        if chain_value & 1 != 0 {
+            return Some(max_sym + 1);
+        }
+        max_sym += 1;
+    }
+}
@@ -0,0 +1,320 @@
+use serde::Serialize;
+use std::collections;
+use std::path::PathBuf;
+
+use crate::core_dump::CoreInfo;
+use crate::demangle;
+use crate::dependencies::{IncompatibleCandidate, Resolution};
+use crate::options::DemangleStyle;
+use crate::resolve_symbols::UnresolvedReason;
+use crate::search_path::SearchPathSource;
+use crate::summarize::{
+    BinaryType, DynamicData, DynamicSymbolReference, ElfSummary, ExportedDynamicSymbol,
+    LoadFootprint, RelocationCounts, SymbolBinding, SymbolType, SymbolVisibility, VersionedSymbol,
+};
+
+/// A dependency in the closure of a binary
+#[derive(Serialize)]
+pub struct DependencyReport {
+    pub name: String,
+    /// The path the dependency resolved to, or `None` if it could not be found
+    pub path: Option<PathBuf>,
+    /// The kind of search path entry the dependency was found through
+    pub source: Option<SearchPathSource>,
+}
+
+/// A dynamic symbol referenced by a binary
+#[derive(Serialize)]
+pub struct SymbolReferenceReport {
+    /// The same as `name_raw` (kept for existing consumers of the JSON output)
+    pub name: String,
+    /// The (possibly mangled) name as it appears in the symbol table
+    pub name_raw: String,
+    /// The fully demangled name (or the raw name, if it is not mangled)
+    pub name_demangled: String,
+    pub version: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: SymbolType,
+    pub binding: SymbolBinding,
+    pub visibility: SymbolVisibility,
+    /// The binary providing the symbol, or `None` if it is unresolved
+    pub provider: Option<PathBuf>,
+    /// The reason the symbol could not be resolved, if it is unresolved
+    pub unresolved_reason: Option<String>,
+}
+
+/// A dynamic symbol defined by a binary
+#[derive(Serialize)]
+pub struct SymbolDefinitionReport {
+    pub name: String,
+    pub name_raw: String,
+    pub name_demangled: String,
+    pub version: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: SymbolType,
+    pub binding: SymbolBinding,
+    pub visibility: SymbolVisibility,
+    pub address: u64,
+    pub size: u64,
+    /// The section the symbol is defined in (e.g., `.text`), or a reserved
+    /// index (`ABS` for absolute symbols, or `COMMON`)
+    pub section: String,
+}
+
+/// The serialized form of everything the text summary reports about a binary
+#[derive(Serialize)]
+pub struct BinaryReport {
+    pub path: PathBuf,
+    pub machine: String,
+    pub bit_size: usize,
+    pub endianness: String,
+    pub linkage: String,
+    /// The SHA-256 of the contents of the file, in hex
+    pub sha256: String,
+    /// The memory mapped for the loadable segments
+    pub load_footprint: LoadFootprint,
+    pub hash_tables: Vec<String>,
+    /// The highest `GLIBC_*` symbol version the binary requires
+    pub minimum_glibc: Option<String>,
+    /// Signs of tampering or corruption (e.g., inconsistent hash tables or
+    /// overlapping segments)
+    pub warnings: Vec<String>,
+    /// The names of the bits set in `DT_FLAGS` and `DT_FLAGS_1`
+    pub dynamic_flags: Vec<String>,
+    /// The number of PLT and other relocations (dynamic binaries only)
+    pub relocations: Option<RelocationCounts>,
+    /// The direct dependencies (`DT_NEEDED` entries) of the binary
+    pub needed: Vec<String>,
+    /// The libraries named by `DT_FILTER` entries, which provide the
+    /// definitions of the symbols of this library
+    pub filters: Vec<String>,
+    /// The libraries named by `DT_AUXILIARY` entries
+    pub auxiliary_filters: Vec<String>,
+    /// The auditing libraries named by `DT_AUDIT` and `DT_DEPAUDIT`
+    pub audit: Vec<String>,
+    pub dependency_audit: Vec<String>,
+    /// The transitive dependency closure of the binary
+    pub dependencies: Vec<DependencyReport>,
+    /// The names of the dependencies in the (breadth-first) order they were
+    /// discovered, which determines how symbols are bound
+    pub resolution_order: Vec<String>,
+    /// Libraries skipped while searching for the dependencies because their
+    /// ELF class or byte order does not match
+    pub incompatible_dependencies: Vec<IncompatibleCandidate>,
+    /// Libraries that were needed, but not searched for because they were
+    /// ignored with `--ignore-dep`
+    pub ignored_dependencies: Vec<String>,
+    /// Libraries that were needed, but not searched for because they are
+    /// beyond `--max-depth`
+    pub depth_limited_dependencies: Vec<String>,
+    /// Direct dependencies that another library in the closure also needs
+    pub also_indirect_dependencies: Vec<String>,
+    pub referenced_symbols: Vec<SymbolReferenceReport>,
+    pub defined_symbols: Vec<SymbolDefinitionReport>,
+    /// The separate debug file that `debug_symbols` were read from (for stripped binaries)
+    pub debug_file: Option<PathBuf>,
+    /// The symbols defined in the static symbol table of the debug file
+    pub debug_symbols: Vec<SymbolDefinitionReport>,
+    /// The crashed process, if the binary is a core dump
+    pub core: Option<CoreInfo>,
+}
+
+fn symbol_reference_report(
+    resolutions: &collections::BTreeMap<VersionedSymbol, &ElfSummary>,
+    unresolved: &collections::BTreeMap<VersionedSymbol, UnresolvedReason>,
+    sym_ref: &DynamicSymbolReference,
+) -> SymbolReferenceReport {
+    SymbolReferenceReport {
+        name: sym_ref.symbol.name.clone(),
+        name_raw: sym_ref.symbol.name.clone(),
+        name_demangled: demangle::demangle(&sym_ref.symbol.name, DemangleStyle::Full),
+        version: sym_ref.symbol.version.clone(),
+        type_: sym_ref.type_,
+        binding: sym_ref.binding,
+        visibility: sym_ref.visibility,
+        provider: resolutions
+            .get(&sym_ref.symbol)
+            .map(|elf| elf.filename.clone()),
+        unresolved_reason: unresolved.get(&sym_ref.symbol).map(|r| r.to_string()),
+    }
+}
+
+fn symbol_definition_report(
+    summary: &ElfSummary,
+    sym_def: &ExportedDynamicSymbol,
+) -> SymbolDefinitionReport {
+    SymbolDefinitionReport {
+        name: sym_def.symbol.name.clone(),
+        name_raw: sym_def.symbol.name.clone(),
+        name_demangled: demangle::demangle(&sym_def.symbol.name, DemangleStyle::Full),
+        version: sym_def.symbol.version.clone(),
+        type_: sym_def.type_,
+        binding: sym_def.binding,
+        visibility: sym_def.visibility,
+        address: sym_def.address,
+        size: sym_def.size,
+        section: summary.section_name(sym_def.section),
+    }
+}
+
+/// One of the lists of libraries in the dynamic section (empty for static binaries)
+fn dynamic_list<F: Fn(&DynamicData) -> &Vec<String>>(
+    summary: &ElfSummary,
+    field: F,
+) -> Vec<String> {
+    match &summary.binary_type {
+        BinaryType::Static => Vec::new(),
+        BinaryType::Dynamic(dyn_data) => field(dyn_data).clone(),
+    }
+}
+
+/// Build the serializable report for a binary
+///
+/// The `resolution` is the dependency closure of the root binary, while the
+/// symbol `resolutions` and `unresolved` diagnostics must cover the references
+/// of `summary`
+pub fn binary_report(
+    summary: &ElfSummary,
+    resolution: &Resolution,
+    resolutions: &collections::BTreeMap<VersionedSymbol, &ElfSummary>,
+    unresolved: &collections::BTreeMap<VersionedSymbol, UnresolvedReason>,
+) -> BinaryReport {
+    let (linkage, minimum_glibc, mut warnings, needed, referenced_symbols, defined_symbols) =
+        match &summary.binary_type {
+            BinaryType::Static => (
+                "static",
+                None,
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            ),
+            BinaryType::Dynamic(dyn_data) => (
+                "dynamic",
+                dyn_data.minimum_glibc_version(),
+                dyn_data.hash_table_warnings(),
+                dyn_data.deps.clone(),
+                dyn_data
+                    .dynamic_symbol_refs
+                    .iter()
+                    .map(|r| symbol_reference_report(resolutions, unresolved, r))
+                    .collect(),
+                dyn_data
+                    .provided_dynamic_symbols
+                    .iter()
+                    .map(|d| symbol_definition_report(summary, d))
+                    .collect(),
+            ),
+        };
+
+    warnings.extend(summary.segment_warnings());
+
+    BinaryReport {
+        path: summary.filename.clone(),
+        machine: summary.machine_name(),
+        bit_size: summary.bit_size,
+        endianness: format!("{:?}", summary.endianness).to_lowercase(),
+        linkage: linkage.to_string(),
+        sha256: summary.sha256.clone(),
+        load_footprint: summary.load_footprint(),
+        hash_tables: summary
+            .hash_tables()
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+        minimum_glibc,
+        warnings,
+        dynamic_flags: match &summary.binary_type {
+            BinaryType::Static => Vec::new(),
+            BinaryType::Dynamic(dyn_data) => dyn_data.flag_names(),
+        },
+        relocations: match &summary.binary_type {
+            BinaryType::Static => None,
+            BinaryType::Dynamic(dyn_data) => Some(dyn_data.relocation_counts),
+        },
+        needed,
+        filters: dynamic_list(summary, |d| &d.filters),
+        auxiliary_filters: dynamic_list(summary, |d| &d.auxiliary_filters),
+        audit: dynamic_list(summary, |d| &d.audit),
+        dependency_audit: dynamic_list(summary, |d| &d.dependency_audit),
+        dependencies: resolution
+            .dependencies
+            .iter()
+            .map(|(name, dep)| DependencyReport {
+                name: name.clone(),
+                path: dep.as_ref().map(|d| d.filename.clone()),
+                source: resolution.sources.get(name).copied(),
+            })
+            .collect(),
+        resolution_order: resolution.order.clone(),
+        incompatible_dependencies: resolution.incompatible.clone(),
+        ignored_dependencies: resolution.ignored.iter().cloned().collect(),
+        depth_limited_dependencies: resolution.depth_limited.iter().cloned().collect(),
+        also_indirect_dependencies: resolution.also_indirect.iter().cloned().collect(),
+        referenced_symbols,
+        defined_symbols,
+        debug_file: summary.debug_file.clone(),
+        debug_symbols: summary
+            .debug_symbols
+            .iter()
+            .map(|d| symbol_definition_report(summary, d))
+            .collect(),
+        core: summary.core.clone(),
+    }
+}
+
+/// The sizes of the interface and dependency closure of a binary (for `--count-only`)
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct Counts {
+    /// The number of libraries in the dependency closure
+    pub dependencies: usize,
+    /// The number of libraries in the closure that could not be found
+    pub unresolved_dependencies: usize,
+    pub defined_symbols: usize,
+    pub referenced_symbols: usize,
+}
+
+impl Counts {
+    pub fn add(&mut self, other: &Counts) {
+        self.dependencies += other.dependencies;
+        self.unresolved_dependencies += other.unresolved_dependencies;
+        self.defined_symbols += other.defined_symbols;
+        self.referenced_symbols += other.referenced_symbols;
+    }
+}
+
+#[derive(Serialize)]
+pub struct CountsReport {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// The counts for each input, along with the totals across all of them
+#[derive(Serialize)]
+pub struct CountsSummary {
+    pub binaries: Vec<CountsReport>,
+    pub total: Counts,
+}
+
+pub fn counts_report(
+    summary: &ElfSummary,
+    deps: &collections::BTreeMap<String, Option<ElfSummary>>,
+) -> CountsReport {
+    let (defined_symbols, referenced_symbols) = match &summary.binary_type {
+        BinaryType::Static => (0, 0),
+        BinaryType::Dynamic(dyn_data) => (
+            dyn_data.provided_dynamic_symbols.len(),
+            dyn_data.dynamic_symbol_refs.len(),
+        ),
+    };
+    CountsReport {
+        path: summary.filename.clone(),
+        counts: Counts {
+            dependencies: deps.len(),
+            unresolved_dependencies: deps.values().filter(|d| d.is_none()).count(),
+            defined_symbols,
+            referenced_symbols,
+        },
+    }
+}
@@ -0,0 +1,216 @@
+//! The cache of library locations (`ld.so.cache`) maintained by `ldconfig`
+//!
+//! The loader consults the cache after `DT_RUNPATH` and before the default
+//! directories.  It maps each SONAME to the path of the library providing it,
+//! which covers the directories configured in `/etc/ld.so.conf` that the
+//! default search path does not include (e.g., `/usr/lib/x86_64-linux-gnu`).
+//!
+//! Only the current format (`glibc-ld.so.cache1.1`) is supported, either on its
+//! own or after the entries of the legacy format (`ld.so-1.7.0`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const OLD_MAGIC: &[u8] = b"ld.so-1.7.0";
+const NEW_MAGIC: &[u8] = b"glibc-ld.so.cache1.1";
+const OLD_HEADER_SIZE: usize = 16;
+const OLD_ENTRY_SIZE: usize = 12;
+const NEW_HEADER_SIZE: usize = 48;
+const NEW_ENTRY_SIZE: usize = 24;
+
+// The type of library, from the low byte of the flags of each entry
+const FLAG_TYPE_MASK: i32 = 0x00ff;
+const FLAG_ELF: i32 = 0x0001;
+const FLAG_ELF_LIBC6: i32 = 0x0003;
+
+// The byte order recorded in the flags of the header
+const ENDIAN_LITTLE: u8 = 2;
+const ENDIAN_BIG: u8 = 3;
+
+#[derive(thiserror::Error, Debug)]
+pub enum LdCacheError {
+    #[error("Not an ld.so.cache file (or an unsupported format)")]
+    UnsupportedFormat,
+    #[error("Truncated ld.so.cache file")]
+    Truncated,
+}
+
+/// The entries of an `ld.so.cache` file, in the order the loader searches them
+#[derive(Default)]
+pub struct LdCache {
+    entries: Vec<(String, PathBuf)>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn u32(&self, offset: usize) -> Result<u32, LdCacheError> {
+        let raw: [u8; 4] = self
+            .bytes
+            .get(offset..offset + 4)
+            .and_then(|b| b.try_into().ok())
+            .ok_or(LdCacheError::Truncated)?;
+        Ok(if self.big_endian {
+            u32::from_be_bytes(raw)
+        } else {
+            u32::from_le_bytes(raw)
+        })
+    }
+
+    /// The NUL-terminated string at `offset`
+    fn string(&self, offset: usize) -> Result<String, LdCacheError> {
+        let rest = self.bytes.get(offset..).ok_or(LdCacheError::Truncated)?;
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(LdCacheError::Truncated)?;
+        Ok(String::from_utf8_lossy(&rest[..len]).into_owned())
+    }
+}
+
+/// The offset of the header of the current format in the file
+fn new_format_offset(bytes: &[u8]) -> Result<usize, LdCacheError> {
+    if bytes.starts_with(NEW_MAGIC) {
+        return Ok(0);
+    }
+    if !bytes.starts_with(OLD_MAGIC) {
+        return Err(LdCacheError::UnsupportedFormat);
+    }
+    // The legacy entries are followed by the current format (aligned to 8
+    // bytes); the legacy header is in native byte order, which is not known
+    // yet, so try both
+    for big_endian in [false, true] {
+        let reader = Reader { bytes, big_endian };
+        let nlibs = reader.u32(12)? as usize;
+        let offset = nlibs
+            .checked_mul(OLD_ENTRY_SIZE)
+            .and_then(|n| n.checked_add(OLD_HEADER_SIZE + 7))
+            .map(|n| n & !7);
+        if let Some(offset) = offset {
+            if bytes
+                .get(offset..)
+                .is_some_and(|b| b.starts_with(NEW_MAGIC))
+            {
+                return Ok(offset);
+            }
+        }
+    }
+    Err(LdCacheError::UnsupportedFormat)
+}
+
+impl LdCache {
+    pub fn parse(bytes: &[u8]) -> Result<LdCache, LdCacheError> {
+        let start = new_format_offset(bytes)?;
+        // String offsets are relative to the start of the current format
+        let bytes = &bytes[start..];
+        let big_endian = match bytes.get(28) {
+            Some(&ENDIAN_BIG) => true,
+            Some(&ENDIAN_LITTLE) | Some(_) => false,
+            None => return Err(LdCacheError::Truncated),
+        };
+        let reader = Reader { bytes, big_endian };
+        let nlibs = reader.u32(20)? as usize;
+        if nlibs > bytes.len() / NEW_ENTRY_SIZE {
+            return Err(LdCacheError::Truncated);
+        }
+
+        let mut entries = Vec::new();
+        for idx in 0..nlibs {
+            let entry = NEW_HEADER_SIZE + idx * NEW_ENTRY_SIZE;
+            let flags = reader.u32(entry)? as i32;
+            if !matches!(flags & FLAG_TYPE_MASK, FLAG_ELF | FLAG_ELF_LIBC6) {
+                continue;
+            }
+            let key = reader.string(reader.u32(entry + 4)? as usize)?;
+            let value = reader.string(reader.u32(entry + 8)? as usize)?;
+            entries.push((key, PathBuf::from(value)));
+        }
+        Ok(LdCache { entries })
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<LdCache> {
+        let bytes = fs::read(path)?;
+        Ok(LdCache::parse(&bytes)?)
+    }
+
+    /// The directories containing the cached libraries for `lib_name`, in
+    /// the order the loader tries them
+    ///
+    /// There can be several (e.g., for different architectures); libraries
+    /// that do not match the binary are rejected like any other candidate.
+    pub fn directories(&self, lib_name: &str) -> Vec<&Path> {
+        self.entries
+            .iter()
+            .filter(|(name, _)| name == lib_name)
+            .filter_map(|(_, path)| path.parent())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a cache in the current format with the given (flags, name, path) entries
+    fn build_cache(entries: &[(i32, &str, &str)]) -> Vec<u8> {
+        let strings_start = NEW_HEADER_SIZE + entries.len() * NEW_ENTRY_SIZE;
+        let mut strings = Vec::new();
+        let mut bytes = NEW_MAGIC.to_vec();
+        bytes.extend((entries.len() as u32).to_le_bytes());
+        bytes.extend([0; 4]);
+        bytes.push(ENDIAN_LITTLE);
+        bytes.resize(NEW_HEADER_SIZE, 0);
+        for (flags, name, path) in entries {
+            let key = strings_start + strings.len();
+            strings.extend(name.as_bytes());
+            strings.push(0);
+            let value = strings_start + strings.len();
+            strings.extend(path.as_bytes());
+            strings.push(0);
+            bytes.extend(flags.to_le_bytes());
+            bytes.extend((key as u32).to_le_bytes());
+            bytes.extend((value as u32).to_le_bytes());
+            bytes.extend([0; 12]);
+        }
+        bytes.extend(strings);
+        bytes
+    }
+
+    #[test]
+    fn cached_directories_are_found_by_soname() {
+        let bytes = build_cache(&[
+            (0x0303, "libc.so.6", "/lib/x86_64-linux-gnu/libc.so.6"),
+            (0x0003, "libc.so.6", "/lib/i386-linux-gnu/libc.so.6"),
+            (0x0303, "libm.so.6", "/lib/x86_64-linux-gnu/libm.so.6"),
+        ]);
+        let cache = LdCache::parse(&bytes).unwrap();
+        assert_eq!(
+            cache.directories("libc.so.6"),
+            vec![
+                Path::new("/lib/x86_64-linux-gnu"),
+                Path::new("/lib/i386-linux-gnu")
+            ]
+        );
+        assert!(cache.directories("libz.so.1").is_empty());
+    }
+
+    #[test]
+    fn the_current_format_is_found_after_legacy_entries() {
+        let mut bytes = OLD_MAGIC.to_vec();
+        bytes.push(0);
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend([0; OLD_ENTRY_SIZE]);
+        bytes.resize(32, 0);
+        bytes.extend(build_cache(&[(0x0303, "libz.so.1", "/usr/lib/libz.so.1")]));
+        let cache = LdCache::parse(&bytes).unwrap();
+        assert_eq!(cache.directories("libz.so.1"), vec![Path::new("/usr/lib")]);
+    }
+
+    #[test]
+    fn other_files_are_rejected() {
+        assert!(LdCache::parse(b"not a cache").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dependencies::Resolution;
+use crate::summarize::{BinaryType, ElfSummary};
+
+/// A library in the dependency closure of a locked binary
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    /// The name the library was requested by (i.e., its `DT_NEEDED` entry)
+    pub name: String,
+    /// The absolute path the library resolved to, or `None` if it could not be found
+    pub path: Option<PathBuf>,
+    pub build_id: Option<String>,
+    pub soname: Option<String>,
+}
+
+/// The resolved dependency closure of a binary, for detecting drift between
+/// environments (e.g., build and deploy)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LockedBinary {
+    /// The absolute path of the binary, which it is matched by when verifying
+    pub path: PathBuf,
+    pub build_id: Option<String>,
+    pub dependencies: Vec<LockedDependency>,
+}
+
+/// A difference between a lockfile and the current resolution of a binary
+#[derive(Debug)]
+pub enum Drift {
+    /// The binary is not listed in the lockfile
+    UnlockedBinary(PathBuf),
+    /// A dependency is in the closure now, but not in the lockfile
+    Added(LockedDependency),
+    /// A dependency in the lockfile is no longer in the closure
+    Removed(LockedDependency),
+    /// A dependency now resolves to a different path
+    PathChanged(String, Option<PathBuf>, Option<PathBuf>),
+    /// A dependency now resolves to a library with a different build ID
+    BuildIdChanged(String, Option<String>, Option<String>),
+}
+
+fn display_path(path: &Option<PathBuf>) -> String {
+    path.as_ref().map_or(String::from("<Unresolved>"), |p| {
+        p.to_string_lossy().into_owned()
+    })
+}
+
+fn display_build_id(build_id: &Option<String>) -> &str {
+    build_id.as_deref().unwrap_or("<None>")
+}
+
+impl std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Drift::UnlockedBinary(path) => {
+                write!(f, "{} is not in the lockfile", path.to_string_lossy())
+            }
+            Drift::Added(dep) => write!(
+                f,
+                "{} is a new dependency (resolved to {})",
+                dep.name,
+                display_path(&dep.path)
+            ),
+            Drift::Removed(dep) => write!(
+                f,
+                "{} is no longer a dependency (was {})",
+                dep.name,
+                display_path(&dep.path)
+            ),
+            Drift::PathChanged(name, locked, current) => write!(
+                f,
+                "{} resolved to {}, but was {}",
+                name,
+                display_path(current),
+                display_path(locked)
+            ),
+            Drift::BuildIdChanged(name, locked, current) => write!(
+                f,
+                "{} has build ID {}, but was {}",
+                name,
+                display_build_id(current),
+                display_build_id(locked)
+            ),
+        }
+    }
+}
+
+/// The absolute path of a binary, so that the lockfile does not depend on the
+/// working directory (or the path used to reach the binary)
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Lock the resolved dependency closure of `root`
+pub fn lock_binary(root: &ElfSummary, resolution: &Resolution) -> LockedBinary {
+    let dependencies = resolution
+        .dependencies
+        .iter()
+        .map(|(name, dep)| LockedDependency {
+            name: name.clone(),
+            path: dep.as_ref().map(|d| canonical_path(&d.filename)),
+            build_id: dep.as_ref().and_then(|d| d.build_id.clone()),
+            soname: dep.as_ref().and_then(|d| match &d.binary_type {
+                BinaryType::Static => None,
+                BinaryType::Dynamic(dyn_data) => dyn_data.soname.clone(),
+            }),
+        })
+        .collect();
+    LockedBinary {
+        path: canonical_path(&root.filename),
+        build_id: root.build_id.clone(),
+        dependencies,
+    }
+}
+
+pub fn read(path: &Path) -> anyhow::Result<Vec<LockedBinary>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Compare the current closure of a binary with the lockfile
+///
+/// The dependencies are matched by name.  The SONAME is only recorded for
+/// reference, since it is part of the contents covered by the build ID
+pub fn verify(locked: &[LockedBinary], current: &LockedBinary) -> Vec<Drift> {
+    let locked = match locked.iter().find(|b| b.path == current.path) {
+        None => return vec![Drift::UnlockedBinary(current.path.clone())],
+        Some(locked) => locked,
+    };
+
+    let mut drift = Vec::new();
+    for dep in &current.dependencies {
+        match locked.dependencies.iter().find(|d| d.name == dep.name) {
+            None => drift.push(Drift::Added(dep.clone())),
+            Some(locked_dep) if locked_dep.path != dep.path => drift.push(Drift::PathChanged(
+                dep.name.clone(),
+                locked_dep.path.clone(),
+                dep.path.clone(),
+            )),
+            Some(locked_dep) if locked_dep.build_id != dep.build_id => {
+                drift.push(Drift::BuildIdChanged(
+                    dep.name.clone(),
+                    locked_dep.build_id.clone(),
+                    dep.build_id.clone(),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    for locked_dep in &locked.dependencies {
+        if !current
+            .dependencies
+            .iter()
+            .any(|d| d.name == locked_dep.name)
+        {
+            drift.push(Drift::Removed(locked_dep.clone()));
+        }
+    }
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies;
+    use crate::ld_cache::LdCache;
+    use crate::summarize;
+
+    fn dependency(name: &str, path: &str, build_id: &str) -> LockedDependency {
+        LockedDependency {
+            name: String::from(name),
+            path: Some(PathBuf::from(path)),
+            build_id: Some(String::from(build_id)),
+            soname: Some(String::from(name)),
+        }
+    }
+
+    fn binary(dependencies: Vec<LockedDependency>) -> LockedBinary {
+        LockedBinary {
+            path: PathBuf::from("/usr/bin/app"),
+            build_id: None,
+            dependencies,
+        }
+    }
+
+    #[test]
+    fn paths_are_absolute() {
+        let summ = summarize::summarize_path(&PathBuf::from("tests/fixtures/dynamic")).unwrap();
+        let resolution = dependencies::resolve_dependencies(
+            &[PathBuf::from("/")],
+            &LdCache::default(),
+            &summ,
+            false,
+            &[],
+            None,
+        );
+        let locked = lock_binary(&summ, &resolution);
+        assert!(locked.path.is_absolute());
+        assert!(locked.path.ends_with("tests/fixtures/dynamic"));
+        assert_eq!(locked.dependencies.len(), 1);
+        let lib = locked.dependencies[0].path.as_ref().unwrap();
+        assert!(lib.is_absolute());
+        assert!(lib.ends_with("tests/fixtures/libfixture.so"));
+    }
+
+    #[test]
+    fn unchanged_closure_has_no_drift() {
+        let current = binary(vec![dependency("libc.so.6", "/usr/lib/libc.so.6", "aa")]);
+        assert!(verify(std::slice::from_ref(&current), &current).is_empty());
+    }
+
+    #[test]
+    fn unlocked_binary_is_reported() {
+        let current = binary(Vec::new());
+        let mut other = current.clone();
+        other.path = PathBuf::from("/usr/bin/other");
+        let drift = verify(&[other], &current);
+        assert_eq!(drift.len(), 1);
+        assert!(matches!(&drift[0], Drift::UnlockedBinary(path) if *path == current.path));
+    }
+
+    #[test]
+    fn changed_dependencies_are_reported() {
+        let locked = binary(vec![
+            dependency("libc.so.6", "/usr/lib/libc.so.6", "aa"),
+            dependency("libm.so.6", "/usr/lib/libm.so.6", "bb"),
+            dependency("libz.so.1", "/usr/lib/libz.so.1", "cc"),
+            dependency("libold.so.1", "/usr/lib/libold.so.1", "dd"),
+        ]);
+        let current = binary(vec![
+            dependency("libc.so.6", "/usr/lib/libc.so.6", "aa"),
+            dependency("libm.so.6", "/opt/lib/libm.so.6", "bb"),
+            dependency("libz.so.1", "/usr/lib/libz.so.1", "ee"),
+            dependency("libnew.so.1", "/usr/lib/libnew.so.1", "ff"),
+        ]);
+        let drift: Vec<String> = verify(&[locked], &current)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            drift,
+            vec![
+                "libm.so.6 resolved to /opt/lib/libm.so.6, but was /usr/lib/libm.so.6",
+                "libz.so.1 has build ID ee, but was cc",
+                "libnew.so.1 is a new dependency (resolved to /usr/lib/libnew.so.1)",
+                "libold.so.1 is no longer a dependency (was /usr/lib/libold.so.1)",
+            ]
+        );
+    }
+}
@@ -1,14 +1,31 @@
+mod abi_baseline;
+mod allowlist;
+mod archive;
+mod columns;
+mod core_dump;
+mod debug_file;
+mod demangle;
 mod dependencies;
+mod hash_table;
+mod json;
+mod ld_cache;
+mod lockfile;
 mod options;
+mod pe;
+mod proc_maps;
 mod resolve_symbols;
+mod sbom;
 mod search_path;
+mod security;
 mod summarize;
+mod timings;
 mod ui;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use object::Endianness;
 use std::collections;
 use std::fs;
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::time::Duration;
 use term_table::row;
@@ -22,106 +39,1798 @@ fn endian_as_str(end: Endianness) -> &'static str {
 
 fn render_dynamic_symbol_ref<'a>(
     resolutions: &collections::BTreeMap<summarize::VersionedSymbol, &'a summarize::ElfSummary>,
+    unresolved: &collections::BTreeMap<
+        summarize::VersionedSymbol,
+        resolve_symbols::UnresolvedReason,
+    >,
+    cols: &[options::SymbolColumn],
+    demangle_style: options::DemangleStyle,
     sym_ref: &summarize::DynamicSymbolReference,
 ) -> Vec<String> {
-    let provider = resolutions
-        .get(&sym_ref.symbol)
-        .map_or(PathBuf::from("<Unresolved>"), |elf| elf.filename.clone());
-    vec![
-        format!("{:?}", sym_ref.type_),
-        format!("{:?}", sym_ref.binding),
-        String::from(&sym_ref.symbol.name),
-        provider.to_string_lossy().into(),
-    ]
-}
-
-fn render_defined_dynamic_symbol(sym_def: &summarize::ExportedDynamicSymbol) -> Vec<String> {
-    vec![
-        format!("{:#x}", sym_def.address),
-        format!("{}", sym_def.size),
-        format!("{:?}", sym_def.type_),
-        format!("{:?}", sym_def.binding),
-        String::from(&sym_def.symbol.name),
-    ]
+    let provider = match resolutions.get(&sym_ref.symbol) {
+        Some(elf) => elf.filename.to_string_lossy().into_owned(),
+        None => unresolved
+            .get(&sym_ref.symbol)
+            .map_or(String::from("<Unresolved>"), |reason| reason.to_string()),
+    };
+    columns::reference_row(sym_ref, &provider, cols, demangle_style)
+}
+
+/// Resolve the symbols referenced by the root binary against its dependencies
+#[allow(clippy::type_complexity)]
+fn resolve_root_symbols<'a>(
+    summary: &summarize::ElfSummary,
+    all_libs: &[&'a summarize::ElfSummary],
+) -> (
+    collections::BTreeMap<summarize::VersionedSymbol, &'a summarize::ElfSummary>,
+    collections::BTreeMap<summarize::VersionedSymbol, resolve_symbols::UnresolvedReason>,
+) {
+    match &summary.binary_type {
+        summarize::BinaryType::Static => {
+            (collections::BTreeMap::new(), collections::BTreeMap::new())
+        }
+        summarize::BinaryType::Dynamic(dyn_data) => timings::time(timings::Phase::Symbols, || {
+            let resolutions =
+                resolve_symbols::resolve_symbols(&dyn_data.dynamic_symbol_refs, all_libs);
+            let unresolved = resolve_symbols::diagnose_unresolved(
+                &dyn_data.dynamic_symbol_refs,
+                all_libs,
+                &resolutions,
+            );
+            (resolutions, unresolved)
+        }),
+    }
+}
+
+fn summary_json_report(
+    summary: &summarize::ElfSummary,
+    resolution: &dependencies::Resolution,
+) -> json::BinaryReport {
+    let all_libs: Vec<&summarize::ElfSummary> = resolution
+        .dependencies
+        .values()
+        .filter_map(|x| x.as_ref())
+        .collect();
+    let (resolutions, unresolved) = resolve_root_symbols(summary, &all_libs);
+    json::binary_report(summary, resolution, &resolutions, &unresolved)
+}
+
+/// Describe the crashed process and the shared libraries it had loaded
+fn render_core(out: &mut dyn Write, core: &core_dump::CoreInfo) -> io::Result<()> {
+    writeln!(
+        out,
+        "  Core dump of {}",
+        core.executable.as_deref().unwrap_or("<Unknown>")
+    )?;
+    if let Some(args) = &core.arguments {
+        writeln!(out, "  Command line: {}", args)?;
+    }
+    match (core.signal, core.signal_name()) {
+        (None, _) => {}
+        (Some(signal), None) => writeln!(out, "  Signal: {}", signal)?,
+        (Some(signal), Some(name)) => writeln!(out, "  Signal: {} ({})", signal, name)?,
+    }
+    writeln!(out, "  Mapped shared libraries:")?;
+    for lib in core.shared_libraries() {
+        writeln!(out, "    {}", lib)?;
+    }
+    Ok(())
 }
 
 fn render_summary(
+    out: &mut dyn Write,
     summary: &summarize::ElfSummary,
-    deps: &collections::BTreeMap<String, Option<summarize::ElfSummary>>,
+    resolution: &dependencies::Resolution,
+    column_spec: &[options::SymbolColumn],
+    human_sizes: bool,
+    demangle_style: options::DemangleStyle,
+    export_types: &[summarize::SymbolType],
 ) -> anyhow::Result<()> {
-    println!(
+    let deps = &resolution.dependencies;
+    writeln!(
+        out,
         "File {} is a {} bit {} endian ELF file",
-        summary.filename.as_path().to_str().unwrap(),
+        summary.filename.as_path().to_string_lossy(),
         summary.bit_size,
         endian_as_str(summary.endianness)
-    );
+    )?;
+    if let Some(core) = &summary.core {
+        render_core(out, core)?;
+        return Ok(());
+    }
+    writeln!(out, "  SHA-256: {}", summary.sha256)?;
+    let footprint = summary.load_footprint();
+    writeln!(
+        out,
+        "  Loadable segments: {} ({} in memory: {} executable, {} writable, {} read-only)",
+        footprint.segments,
+        columns::format_size(footprint.total, human_sizes),
+        columns::format_size(footprint.executable, human_sizes),
+        columns::format_size(footprint.writable, human_sizes),
+        columns::format_size(footprint.read_only, human_sizes)
+    )?;
+    for warning in summary.segment_warnings() {
+        writeln!(out, "  Warning: {}", warning)?;
+    }
     match &summary.binary_type {
         summarize::BinaryType::Static => {
-            println!("  Static");
+            writeln!(out, "  Static")?;
         }
         summarize::BinaryType::Dynamic(dyn_deps) => {
-            println!("  Dynamically linked against:");
+            writeln!(
+                out,
+                "  Symbol hash tables: {}",
+                columns::format_hash_tables(summary)
+            )?;
+            writeln!(
+                out,
+                "  Exports: {} functions, {} objects",
+                dyn_deps.exported_functions().count(),
+                dyn_deps.exported_objects().count()
+            )?;
+            writeln!(
+                out,
+                "  Relocations: {} PLT, {} dynamic",
+                dyn_deps.relocation_counts.plt, dyn_deps.relocation_counts.dynamic
+            )?;
+            if let Some(glibc) = dyn_deps.minimum_glibc_version() {
+                writeln!(out, "  Minimum glibc version: {}", glibc)?;
+            }
+            let flags = dyn_deps.flag_names();
+            if !flags.is_empty() {
+                writeln!(out, "  Dynamic flags: {}", flags.join(" "))?;
+            }
+            for (tag, libs) in dyn_deps.filters_and_auditors() {
+                writeln!(out, "  {}: {}", tag, libs.join(", "))?;
+            }
+            for warning in dyn_deps.hash_table_warnings() {
+                writeln!(out, "  Warning: {}", warning)?;
+            }
+            writeln!(out, "  Dynamically linked against:")?;
 
             for (dep_name, dep_summary) in deps {
                 match dep_summary {
-                    None => {
-                        println!("    {} -> Unresolved", dep_name)
-                    }
+                    None => match resolution.failures.get(dep_name) {
+                        None => writeln!(out, "    {} -> Unresolved", dep_name)?,
+                        Some(err) => writeln!(out, "    {} -> Unresolved: {}", dep_name, err)?,
+                    },
                     Some(dep_summary) => {
-                        // Resolve symbolic links before display
-                        let disp_path = fs::canonicalize(dep_summary.filename.as_path())?;
-                        println!(
-                            "    {} -> {}",
-                            dep_name,
-                            disp_path.as_path().to_string_lossy()
-                        );
+                        // Show the path that the search found and, if it is a
+                        // symbolic link, its target; failing to resolve the link
+                        // should not prevent the rest of the report from rendering
+                        let found_path = dep_summary.filename.as_path();
+                        let source = resolution
+                            .sources
+                            .get(dep_name)
+                            .map_or(String::new(), |s| format!(" [{}]", s));
+                        match fs::canonicalize(found_path) {
+                            Ok(target) if target != found_path => writeln!(
+                                out,
+                                "    {} -> {} => {}{}",
+                                dep_name,
+                                found_path.to_string_lossy(),
+                                target.as_path().to_string_lossy(),
+                                source
+                            )?,
+                            _ => writeln!(
+                                out,
+                                "    {} -> {}{}",
+                                dep_name,
+                                found_path.to_string_lossy(),
+                                source
+                            )?,
+                        }
                     }
                 }
             }
 
-            let all_libs = deps.values().filter_map(|x| x.as_ref()).collect();
-            let symbol_resolutions =
-                resolve_symbols::resolve_symbols(&dyn_deps.dynamic_symbol_refs, &all_libs);
+            for (dep_name, found_path) in &resolution.excluded {
+                writeln!(
+                    out,
+                    "    {} -> {} (excluded: not a system library)",
+                    dep_name,
+                    found_path.to_string_lossy()
+                )?;
+            }
+
+            for dep_name in &resolution.ignored {
+                writeln!(out, "    {} (ignored)", dep_name)?;
+            }
+
+            for dep_name in &resolution.depth_limited {
+                writeln!(out, "    {} (not explored: depth limit)", dep_name)?;
+            }
+
+            for candidate in &resolution.incompatible {
+                writeln!(out, "  Warning: {}", candidate)?;
+            }
+
+            let all_libs: Vec<&summarize::ElfSummary> =
+                deps.values().filter_map(|x| x.as_ref()).collect();
+            let (symbol_resolutions, unresolved_symbols) = resolve_root_symbols(summary, &all_libs);
 
-            println!("  Depends on dynamic symbols:");
+            writeln!(out, "  Depends on dynamic symbols:")?;
+            let ref_cols = columns::reference_columns(column_spec);
             let mut sym_ref_table = term_table::Table::new();
-            sym_ref_table.add_row(row::Row::new(vec!["Type", "Binding", "Symbol", "Provider"]));
+            sym_ref_table.add_row(row::Row::new(columns::headers(&ref_cols)));
             for sym_ref in &dyn_deps.dynamic_symbol_refs {
                 sym_ref_table.add_row(row::Row::new(render_dynamic_symbol_ref(
                     &symbol_resolutions,
+                    &unresolved_symbols,
+                    &ref_cols,
+                    demangle_style,
                     sym_ref,
                 )));
             }
-            println!("{}", sym_ref_table.render());
+            writeln!(out, "{}", sym_ref_table.render())?;
+
+            // Only the exports of the requested types are shown (or all of them, if none were requested)
+            let defined: Vec<&summarize::ExportedDynamicSymbol> = dyn_deps
+                .provided_dynamic_symbols
+                .iter()
+                .filter(|d| export_types.is_empty() || export_types.contains(&d.type_))
+                .collect();
+            if !defined.is_empty() {
+                writeln!(out, "  Defines dynamic symbols:")?;
+                let table =
+                    render_definitions(summary, &defined, column_spec, human_sizes, demangle_style);
+                writeln!(out, "{}", table)?;
+            }
+        }
+    }
+
+    if let Some(debug_file) = &summary.debug_file {
+        let defined: Vec<&summarize::ExportedDynamicSymbol> = summary
+            .debug_symbols
+            .iter()
+            .filter(|d| export_types.is_empty() || export_types.contains(&d.type_))
+            .collect();
+        writeln!(
+            out,
+            "  Symbols from debug file {}:",
+            debug_file.to_string_lossy()
+        )?;
+        let table = render_definitions(summary, &defined, column_spec, human_sizes, demangle_style);
+        writeln!(out, "{}", table)?;
+    }
+    Ok(())
+}
+
+/// Render the table of symbols defined by `summary`
+fn render_definitions(
+    summary: &summarize::ElfSummary,
+    defined: &[&summarize::ExportedDynamicSymbol],
+    column_spec: &[options::SymbolColumn],
+    human_sizes: bool,
+    demangle_style: options::DemangleStyle,
+) -> String {
+    let def_cols = columns::definition_columns(column_spec);
+    let mut table = term_table::Table::new();
+    table.add_row(row::Row::new(columns::headers(&def_cols)));
+    for sym_def in defined {
+        table.add_row(row::Row::new(columns::definition_row(
+            summary,
+            sym_def,
+            &def_cols,
+            human_sizes,
+            demangle_style,
+        )));
+    }
+    table.render()
+}
+
+fn render_security_table(out: &mut dyn Write, report: &security::SecurityReport) -> io::Result<()> {
+    writeln!(out, "File {}", report.file.as_path().to_string_lossy())?;
+    let mut table = term_table::Table::new();
+    table.add_row(row::Row::new(vec!["Property", "Status"]));
+    table.add_row(row::Row::new(vec![
+        String::from("NX"),
+        report.nx.to_string(),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("PIE"),
+        format!("{:?}", report.pie),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("RELRO"),
+        format!("{:?}", report.relro),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("Stack Canary"),
+        report.canary.to_string(),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("FORTIFY_SOURCE"),
+        report.fortify.to_string(),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("CET"),
+        format!("{:?}", report.cet),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("BTI"),
+        report.bti.to_string(),
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("TEXTREL"),
+        if report.textrel {
+            String::from("TEXTREL present")
+        } else {
+            String::from("None")
+        },
+    ]));
+    table.add_row(row::Row::new(vec![
+        String::from("Insecure RPATH"),
+        if report.insecure_rpaths.is_empty() {
+            String::from("None")
+        } else {
+            report
+                .insecure_rpaths
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+    ]));
+    writeln!(out, "{}", table.render())?;
+    Ok(())
+}
+
+fn render_security(
+    out: &mut dyn Write,
+    summaries: &[&summarize::ElfSummary],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let reports: Vec<security::SecurityReport> = summaries
+        .iter()
+        .map(|s| security::security_report(s))
+        .collect();
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            for report in &reports {
+                render_security_table(out, report)?;
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&reports)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for report in &reports {
+                writeln!(
+                    out,
+                    "{} nx={} pie={:?} relro={:?} canary={} fortify={} cet={:?} bti={} textrel={} insecure_rpaths={}",
+                    report.file.as_path().to_string_lossy(),
+                    report.nx,
+                    report.pie,
+                    report.relro,
+                    report.canary,
+                    report.fortify,
+                    report.cet,
+                    report.bti,
+                    report.textrel,
+                    report.insecure_rpaths.len()
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the dynamic strings, comment, and notes of the binary
+fn render_strings(
+    out: &mut dyn Write,
+    strings: &summarize::BinaryStrings,
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let path_str = strings.file.as_path().to_string_lossy();
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(out, "Strings in {}:", path_str)?;
+            writeln!(out, "  Dynamic strings:")?;
+            for s in &strings.dynstr {
+                writeln!(out, "    {}", s)?;
+            }
+            writeln!(out, "  Comment:")?;
+            for s in &strings.comment {
+                writeln!(out, "    {}", s)?;
+            }
+            writeln!(out, "  Notes:")?;
+            for note in &strings.notes {
+                let type_name = note
+                    .type_name()
+                    .map_or(format!("{:#x}", note.type_), String::from);
+                writeln!(
+                    out,
+                    "    {} {} {}: {}",
+                    note.section, note.owner, type_name, note.descriptor
+                )?;
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(strings)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for s in &strings.dynstr {
+                writeln!(out, "{} dynstr {}", path_str, s)?;
+            }
+            for s in &strings.comment {
+                writeln!(out, "{} comment {}", path_str, s)?;
+            }
+            for note in &strings.notes {
+                writeln!(
+                    out,
+                    "{} note {} {}",
+                    path_str, note.section, note.descriptor
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the `count` largest symbols defined by the binary
+fn render_top_symbols(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    count: usize,
+    style: options::DemangleStyle,
+    human_sizes: bool,
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let mut symbols: Vec<&summarize::ExportedDynamicSymbol> = match &summary.binary_type {
+        summarize::BinaryType::Static => Vec::new(),
+        summarize::BinaryType::Dynamic(dyn_data) => {
+            dyn_data.provided_dynamic_symbols.iter().collect()
+        }
+    };
+    symbols.sort_by_key(|sym| std::cmp::Reverse(sym.size));
+    symbols.truncate(count);
+
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(
+                out,
+                "Largest dynamic symbols in {}:",
+                summary.filename.as_path().to_string_lossy()
+            )?;
+            let mut table = term_table::Table::new();
+            table.add_row(row::Row::new(vec!["Size", "Type", "Address", "Symbol"]));
+            for sym in symbols {
+                table.add_row(row::Row::new(vec![
+                    columns::format_size(sym.size, human_sizes),
+                    format!("{:?}", sym.type_),
+                    summary.format_address(sym.address),
+                    demangle::demangle(&sym.symbol.name, style),
+                ]));
+            }
+            writeln!(out, "{}", table.render())?;
+        }
+        options::OutputFormat::Json => {
+            let entries: Vec<serde_json::Value> = symbols
+                .iter()
+                .map(|sym| {
+                    serde_json::json!({
+                        "name": sym.symbol.name,
+                        "version": sym.symbol.version,
+                        "demangled": demangle::demangle(&sym.symbol.name, style),
+                        "size": sym.size,
+                        "type": format!("{:?}", sym.type_),
+                        "address": sym.address,
+                    })
+                })
+                .collect();
+            writeln!(out, "{}", serde_json::to_string_pretty(&entries)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for sym in symbols {
+                writeln!(
+                    out,
+                    "{} {} {:?} {}",
+                    summary.format_address(sym.address),
+                    columns::format_size(sym.size, human_sizes),
+                    sym.type_,
+                    demangle::demangle(&sym.symbol.name, style)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The exit status used when any input could not be analyzed
+const EXIT_INPUT_FAILED: i32 = 1;
+
+/// The exit status used when a binary fails a requested check (e.g., `--require`)
+///
+/// This is distinct from the status for internal errors (1) and usage errors (2)
+const EXIT_CHECK_FAILED: i32 = 3;
+
+/// Check the binary against the hardening requirements, returning true if all are met
+///
+/// Nothing is printed on success, so that this can be used as a quiet gate in
+/// scripts; unmet requirements are reported on stderr
+fn check_security_requirements(
+    summary: &summarize::ElfSummary,
+    reqs: &[options::SecurityRequirement],
+) -> bool {
+    let report = security::security_report(summary);
+    let unmet = security::unmet_requirements(&report, reqs);
+    if unmet.is_empty() {
+        return true;
+    }
+
+    let names: Vec<String> = unmet
+        .iter()
+        .filter_map(|req| req.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    eprintln!(
+        "{}: missing required hardening: {}",
+        report.file.as_path().to_string_lossy(),
+        names.join(", ")
+    );
+    false
+}
+
+/// Print a hexdump of the bytes, with offsets (relative to the start of the
+/// bytes), sixteen bytes per line in groups of four, and an ASCII column
+fn render_hexdump(out: &mut dyn Write, bytes: &[u8]) -> io::Result<()> {
+    for (line_idx, line) in bytes.chunks(16).enumerate() {
+        let mut hex = String::new();
+        for (idx, b) in line.iter().enumerate() {
+            if idx > 0 && idx % 4 == 0 {
+                hex.push(' ');
+            }
+            hex.push_str(&format!("{:02x}", b));
+        }
+        let ascii: String = line
+            .iter()
+            .map(|b| {
+                if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(out, "  {:08x}  {:<35}  {}", line_idx * 16, hex, ascii)?;
+    }
+    Ok(())
+}
+
+/// Report the symbols referenced by the binary that are not in the allowlist
+///
+/// Returns true if there are no violations
+fn check_allowed_symbols(summary: &summarize::ElfSummary, allowed: &allowlist::Allowlist) -> bool {
+    let violations = allowlist::violations(summary, allowed);
+    for sym in &violations {
+        eprintln!(
+            "{}: references symbol not in the allowlist: {}",
+            summary.filename.as_path().to_string_lossy(),
+            sym
+        );
+    }
+    violations.is_empty()
+}
+
+fn render_interpositions(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    interposed: &[resolve_symbols::Interposition],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(
+                out,
+                "Symbols referenced by {} that would be interposed by preloaded libraries:",
+                summary.filename.to_string_lossy()
+            )?;
+            for interposition in interposed {
+                let replaces = interposition
+                    .replaces
+                    .as_ref()
+                    .map_or(String::from("otherwise unresolved"), |p| {
+                        format!("instead of {}", p.to_string_lossy())
+                    });
+                writeln!(
+                    out,
+                    "  {} -> {} ({})",
+                    interposition.symbol,
+                    interposition.preload.to_string_lossy(),
+                    replaces
+                )?;
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(interposed)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for interposition in interposed {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    summary.filename.to_string_lossy(),
+                    interposition.symbol,
+                    interposition.preload.to_string_lossy()
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_satisfaction(
+    out: &mut dyn Write,
+    reports: &[resolve_symbols::SatisfactionReport],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            for report in reports {
+                writeln!(
+                    out,
+                    "{} satisfies {} of {} references from {}",
+                    report.provider.to_string_lossy(),
+                    report.satisfied.len(),
+                    report.satisfied.len() + report.unsatisfied.len(),
+                    report.consumer.to_string_lossy()
+                )?;
+                for unsat in &report.unsatisfied {
+                    if unsat.available_versions.is_empty() {
+                        writeln!(out, "  {} (not defined)", unsat.symbol)?;
+                    } else {
+                        writeln!(
+                            out,
+                            "  {} (only found {})",
+                            unsat.symbol,
+                            unsat.available_versions.join(", ")
+                        )?;
+                    }
+                }
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(reports)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for report in reports {
+                for unsat in &report.unsatisfied {
+                    writeln!(
+                        out,
+                        "{} {}",
+                        report.consumer.to_string_lossy(),
+                        unsat.symbol
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the dependencies of `summ` (with the given `prefix`), recursively
+///
+/// Libraries that appear more than once in the closure are only expanded the
+/// first time, so shared subtrees are not repeated.  Direct dependencies of the
+/// root that another library also needs are marked, since the tree does not
+/// otherwise show that edge.
+fn render_tree_children(
+    out: &mut dyn Write,
+    summ: &summarize::ElfSummary,
+    resolution: &dependencies::Resolution,
+    prefix: &str,
+    expanded: &mut collections::HashSet<String>,
+) -> io::Result<()> {
+    let needed: &[String] = match &summ.binary_type {
+        summarize::BinaryType::Static => &[],
+        summarize::BinaryType::Dynamic(dyn_data) => &dyn_data.deps,
+    };
+    let is_root = prefix.is_empty();
+    for (idx, name) in needed.iter().enumerate() {
+        let (branch, indent) = if idx + 1 == needed.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let note = if is_root && resolution.also_indirect.contains(name) {
+            " (also indirect)"
+        } else {
+            ""
+        };
+        match resolution.dependencies.get(name).and_then(|d| d.as_ref()) {
+            None if resolution.excluded.contains_key(name) => {
+                writeln!(out, "{}{}{} (excluded)", prefix, branch, name)?;
+            }
+            None if resolution.ignored.contains(name) => {
+                writeln!(out, "{}{}{} (ignored)", prefix, branch, name)?;
+            }
+            None if resolution.depth_limited.contains(name) => {
+                writeln!(
+                    out,
+                    "{}{}{} (not explored: depth limit)",
+                    prefix, branch, name
+                )?;
+            }
+            None => writeln!(out, "{}{}{} (unresolved)", prefix, branch, name)?,
+            Some(dep) if !expanded.insert(name.clone()) => {
+                writeln!(
+                    out,
+                    "{}{}{} => {} (see above){}",
+                    prefix,
+                    branch,
+                    name,
+                    dep.filename.to_string_lossy(),
+                    note
+                )?;
+            }
+            Some(dep) => {
+                writeln!(
+                    out,
+                    "{}{}{} => {}{}",
+                    prefix,
+                    branch,
+                    name,
+                    dep.filename.to_string_lossy(),
+                    note
+                )?;
+                let child_prefix = format!("{}{}", prefix, indent);
+                render_tree_children(out, dep, resolution, &child_prefix, expanded)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_dependency_tree(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    resolution: &dependencies::Resolution,
+) -> io::Result<()> {
+    writeln!(out, "{}", summary.filename.to_string_lossy())?;
+    let mut expanded = collections::HashSet::new();
+    render_tree_children(out, summary, resolution, "", &mut expanded)?;
+    Ok(())
+}
+
+/// Summarize a binary on a single line: path, architecture, kind, linkage,
+/// number of dependencies in the closure, and number of unresolved dependencies
+fn render_oneline(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    resolution: &dependencies::Resolution,
+) -> io::Result<()> {
+    let kind = match summary.file_type {
+        object::elf::ET_EXEC => "exe",
+        object::elf::ET_DYN => match security::pie(summary) {
+            security::Pie::Yes => "pie",
+            _ => "so",
+        },
+        object::elf::ET_REL => "rel",
+        object::elf::ET_CORE => "core",
+        _ => "unknown",
+    };
+    let linkage = match summary.binary_type {
+        summarize::BinaryType::Static => "static",
+        summarize::BinaryType::Dynamic(_) => "dynamic",
+    };
+    let num_unresolved = resolution
+        .dependencies
+        .values()
+        .filter(|d| d.is_none())
+        .count();
+    writeln!(
+        out,
+        "{} {} {} {} deps={} unresolved={}",
+        summary.filename.as_path().to_string_lossy(),
+        summary.machine_name(),
+        kind,
+        linkage,
+        resolution.dependencies.len(),
+        num_unresolved
+    )?;
+    Ok(())
+}
+
+/// List the dynamic symbols of a binary like `nm -D`: the address, the type
+/// letter, and the name of each defined symbol, and the undefined references
+/// (`U`, or `w`/`v` for weak ones) without an address, all sorted by name
+///
+/// With `by_address`, the defined symbols are listed in their (address) order
+/// instead, followed by the undefined references as a separate group.  With
+/// several inputs, the symbols of each are preceded by its path (as `nm` does)
+fn render_nm(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    by_address: bool,
+    with_header: bool,
+) -> io::Result<()> {
+    if with_header {
+        writeln!(out, "\n{}:", summary.filename.to_string_lossy())?;
+    }
+    let dyn_data = match &summary.binary_type {
+        summarize::BinaryType::Static => return Ok(()),
+        summarize::BinaryType::Dynamic(dyn_data) => dyn_data,
+    };
+    let width = if summary.bit_size == 32 { 8 } else { 16 };
+
+    let mut lines: Vec<(&summarize::VersionedSymbol, String, char)> = Vec::new();
+    for def in &dyn_data.provided_dynamic_symbols {
+        let address = format!("{:0width$x}", def.address, width = width);
+        lines.push((&def.symbol, address, summary.nm_letter(def)));
+    }
+    for sym_ref in &dyn_data.dynamic_symbol_refs {
+        lines.push((&sym_ref.symbol, " ".repeat(width), sym_ref.nm_letter()));
+    }
+    if !by_address {
+        lines.sort_by(|a, b| a.0.cmp(b.0));
+    }
+
+    for (symbol, address, letter) in lines {
+        writeln!(out, "{} {} {}", address, letter, symbol)?;
+    }
+    Ok(())
+}
+
+fn render_version_compatibility(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    compat: &[dependencies::VersionCompatibility],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let join_or = |versions: &[String], empty: &str| {
+        if versions.is_empty() {
+            String::from(empty)
+        } else {
+            versions.join(", ")
+        }
+    };
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(
+                out,
+                "Symbol versions required by {}:",
+                summary.filename.as_path().to_string_lossy()
+            )?;
+            for lib in compat {
+                let path = lib.path.as_ref().map_or(String::from("Unresolved"), |p| {
+                    p.to_string_lossy().into_owned()
+                });
+                writeln!(out, "  {} -> {}", lib.library, path)?;
+                writeln!(out, "    Required: {}", join_or(&lib.required, "None"))?;
+                writeln!(out, "    Provided: {}", join_or(&lib.provided, "None"))?;
+                if !lib.missing.is_empty() {
+                    writeln!(out, "    Missing:  {}", lib.missing.join(", "))?;
+                }
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(compat)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for lib in compat {
+                for version in &lib.required {
+                    let status = if lib.missing.contains(version) {
+                        "missing"
+                    } else {
+                        "ok"
+                    };
+                    writeln!(out, "{} {} {}", lib.library, version, status)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_abi_changes(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    changes: &[abi_baseline::AbiChange],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            if changes.is_empty() {
+                writeln!(
+                    out,
+                    "No ABI changes in {}",
+                    summary.filename.as_path().to_string_lossy()
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "ABI changes in {}:",
+                    summary.filename.as_path().to_string_lossy()
+                )?;
+                for change in changes {
+                    writeln!(out, "  {}", change)?;
+                }
+            }
+        }
+        options::OutputFormat::Json => {
+            let report = serde_json::json!({
+                "path": summary.filename,
+                "changes": changes,
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for change in changes {
+                writeln!(
+                    out,
+                    "{} {}",
+                    summary.filename.as_path().to_string_lossy(),
+                    change
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
 
-            if !dyn_deps.provided_dynamic_symbols.is_empty() {
-                println!("  Defines dynamic symbols:");
-                let mut sym_def_table = term_table::Table::new();
-                sym_def_table.add_row(row::Row::new(vec![
-                    "Address", "Size", "Type", "Binding", "Symbol",
+fn render_needed_by(
+    out: &mut dyn Write,
+    lib_name: &str,
+    dependents: &[&summarize::ElfSummary],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let paths: Vec<&PathBuf> = dependents.iter().map(|s| &s.filename).collect();
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            if paths.is_empty() {
+                writeln!(
+                    out,
+                    "{} is not needed by any module in the closure",
+                    lib_name
+                )?;
+            } else {
+                writeln!(out, "{} is needed by:", lib_name)?;
+                for path in paths {
+                    writeln!(out, "  {}", path.as_path().to_string_lossy())?;
+                }
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&paths)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for path in paths {
+                writeln!(out, "{}", path.as_path().to_string_lossy())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_closure_size(
+    out: &mut dyn Write,
+    size: &dependencies::ClosureSize,
+    human: bool,
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(
+                out,
+                "Closure size of {}: {} ({} files)",
+                size.root.to_string_lossy(),
+                columns::format_size(size.total, human),
+                size.files.len()
+            )?;
+            for file in &size.files {
+                writeln!(
+                    out,
+                    "  {:>10}  {}",
+                    columns::format_size(file.size, human),
+                    file.path.to_string_lossy()
+                )?;
+            }
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(size)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            writeln!(
+                out,
+                "{} {}",
+                size.root.to_string_lossy(),
+                columns::format_size(size.total, human)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn render_dependency_usage(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    usage: &[dependencies::DependencyUsage],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            writeln!(
+                out,
+                "Symbols satisfied by the dependencies of {}:",
+                summary.filename.as_path().to_string_lossy()
+            )?;
+            let mut table = term_table::Table::new();
+            table.add_row(row::Row::new(vec!["Dependency", "Path", "Symbols"]));
+            for dep in usage {
+                let path = dep.path.as_ref().map_or(String::from("Unresolved"), |p| {
+                    p.to_string_lossy().into_owned()
+                });
+                table.add_row(row::Row::new(vec![
+                    dep.library.clone(),
+                    path,
+                    dep.satisfied.to_string(),
                 ]));
-                for sym_def in &dyn_deps.provided_dynamic_symbols {
-                    sym_def_table.add_row(row::Row::new(render_defined_dynamic_symbol(sym_def)));
+            }
+            writeln!(out, "{}", table.render())?;
+        }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(usage)?)?;
+        }
+        options::OutputFormat::Oneline => {
+            for dep in usage {
+                writeln!(out, "{} {}", dep.library, dep.satisfied)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The references to explain for `--explain-symbol`
+///
+/// These are the root's references to the symbol (with the version, if one is
+/// given); if it has none, the symbol is explained as if it were referenced
+fn explained_references(
+    summary: &summarize::ElfSummary,
+    symbol: &str,
+) -> Vec<summarize::VersionedSymbol> {
+    let requested = match symbol.split_once('@') {
+        None => summarize::VersionedSymbol {
+            name: symbol.to_string(),
+            version: None,
+        },
+        Some((name, version)) => summarize::VersionedSymbol {
+            name: name.to_string(),
+            version: Some(version.trim_start_matches('@').to_string()),
+        },
+    };
+    let mut refs: Vec<summarize::VersionedSymbol> = match &summary.binary_type {
+        summarize::BinaryType::Static => Vec::new(),
+        summarize::BinaryType::Dynamic(dyn_data) => dyn_data
+            .dynamic_symbol_refs
+            .iter()
+            .map(|r| r.symbol.clone())
+            .filter(|r| {
+                r.name == requested.name
+                    && (requested.version.is_none() || r.version == requested.version)
+            })
+            .collect(),
+    };
+    if refs.is_empty() {
+        refs.push(requested);
+    }
+    refs
+}
+
+fn render_explanation(
+    out: &mut dyn Write,
+    summary: &summarize::ElfSummary,
+    resolution: &dependencies::Resolution,
+    symbol: &str,
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let all_libs: Vec<&summarize::ElfSummary> = resolution
+        .dependencies
+        .values()
+        .filter_map(|x| x.as_ref())
+        .collect();
+    let mut json_explanations = Vec::new();
+    for req in explained_references(summary, symbol) {
+        let candidates = resolve_symbols::explain_resolution(&req, &all_libs);
+        match format {
+            options::OutputFormat::Text
+            | options::OutputFormat::TreeText
+            | options::OutputFormat::Nm
+            | options::OutputFormat::Sbom => {
+                writeln!(
+                    out,
+                    "Resolving {} for {}:",
+                    req,
+                    summary.filename.as_path().to_string_lossy()
+                )?;
+                for (idx, candidate) in candidates.iter().enumerate() {
+                    let definitions: Vec<String> = candidate
+                        .definitions
+                        .iter()
+                        .map(|def| match &def.version {
+                            None => format!("{:?}", def.binding),
+                            Some(v) => format!("{} {:?}", v, def.binding),
+                        })
+                        .collect();
+                    let defined = if definitions.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [{}]", definitions.join(", "))
+                    };
+                    writeln!(
+                        out,
+                        "  {}. {}{}: {}",
+                        idx + 1,
+                        candidate.library.as_path().to_string_lossy(),
+                        defined,
+                        candidate.outcome
+                    )?;
+                }
+                if !candidates
+                    .iter()
+                    .any(|c| c.outcome == resolve_symbols::CandidateOutcome::Selected)
+                {
+                    writeln!(out, "  Unresolved")?;
                 }
+            }
+            options::OutputFormat::Json => {
+                json_explanations.push(serde_json::json!({
+                    "binary": summary.filename,
+                    "symbol": req.name,
+                    "version": req.version,
+                    "candidates": candidates,
+                }));
+            }
+            options::OutputFormat::Oneline => {
+                for candidate in &candidates {
+                    writeln!(
+                        out,
+                        "{} {} {:?}",
+                        req,
+                        candidate.library.as_path().to_string_lossy(),
+                        candidate.outcome
+                    )?;
+                }
+            }
+        }
+    }
+    if !json_explanations.is_empty() {
+        writeln!(out, "{}", serde_json::to_string_pretty(&json_explanations)?)?;
+    }
+    Ok(())
+}
+
+fn render_counts_line(out: &mut dyn Write, label: &str, counts: &json::Counts) -> io::Result<()> {
+    writeln!(
+        out,
+        "{} deps={} unresolved={} defined={} referenced={}",
+        label,
+        counts.dependencies,
+        counts.unresolved_dependencies,
+        counts.defined_symbols,
+        counts.referenced_symbols
+    )?;
+    Ok(())
+}
 
-                println!("{}", sym_def_table.render());
+fn render_counts(
+    out: &mut dyn Write,
+    reports: Vec<json::CountsReport>,
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    let mut total = json::Counts::default();
+    for report in &reports {
+        total.add(&report.counts);
+    }
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::Oneline
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            for report in &reports {
+                render_counts_line(out, &report.path.to_string_lossy(), &report.counts)?;
+            }
+            render_counts_line(out, "total", &total)?;
+        }
+        options::OutputFormat::Json => {
+            let summary = json::CountsSummary {
+                binaries: reports,
+                total,
+            };
+            writeln!(out, "{}", serde_json::to_string_pretty(&summary)?)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_unresolved(
+    out: &mut dyn Write,
+    items: &[dependencies::UnresolvedItem],
+    format: options::OutputFormat,
+) -> anyhow::Result<()> {
+    match format {
+        options::OutputFormat::Text
+        | options::OutputFormat::Oneline
+        | options::OutputFormat::TreeText
+        | options::OutputFormat::Nm
+        | options::OutputFormat::Sbom => {
+            for item in items {
+                writeln!(
+                    out,
+                    "{} {} {}: {}",
+                    item.needed_by.as_path().to_string_lossy(),
+                    item.kind,
+                    item.name,
+                    item.reason
+                )?;
             }
         }
+        options::OutputFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(items)?)?;
+        }
     }
     Ok(())
 }
 
+/// Read the symbols of a stripped input from its separate debug file (if any
+/// debug directories were given); failures are only warnings, since the rest
+/// of the summary is still useful
+fn merge_debug_symbols(summary: &mut summarize::ElfSummary, debug_dirs: &[PathBuf]) {
+    if debug_dirs.is_empty() || !summary.is_stripped() {
+        return;
+    }
+    match debug_file::merge_debug_symbols(summary, debug_dirs) {
+        Ok(true) => {}
+        Ok(false) => log::debug!(
+            "{}: no separate debug file found",
+            summary.filename.to_string_lossy()
+        ),
+        Err(err) => eprintln!(
+            "Warning: {}: could not read the debug file: {}",
+            summary.filename.to_string_lossy(),
+            err
+        ),
+    }
+}
+
+/// Returns true if the binary is built for the `expected` architecture
+///
+/// The architecture is named as in `ElfSummary::machine_name`, but the
+/// common aliases used by other toolchains are also accepted
+fn check_architecture(summary: &summarize::ElfSummary, expected: &str) -> bool {
+    let expected = match expected.to_lowercase().as_str() {
+        "amd64" | "x86-64" | "x64" => String::from("x86_64"),
+        "arm64" => String::from("aarch64"),
+        "i386" | "i486" | "i586" | "i686" => String::from("x86"),
+        other => other.to_string(),
+    };
+    let actual = summary.machine_name();
+    if actual == expected {
+        return true;
+    }
+    eprintln!(
+        "{}: expected the {} architecture, but it is built for {}",
+        summary.filename.to_string_lossy(),
+        expected,
+        actual
+    );
+    false
+}
+
+/// Read the `ld.so.cache` to look up libraries in
+///
+/// This is the file given with `--ld-cache` or, by default, the cache under the
+/// first sysroot that has one (a cache that cannot be read is skipped)
+fn load_ld_cache(args: &options::Options) -> anyhow::Result<ld_cache::LdCache> {
+    if let Some(path) = &args.ld_cache {
+        return ld_cache::LdCache::read(path);
+    }
+    for sysroot in &args.sysroot {
+        let path = sysroot.join("etc/ld.so.cache");
+        if !path.exists() {
+            continue;
+        }
+        match ld_cache::LdCache::read(&path) {
+            Ok(cache) => return Ok(cache),
+            Err(err) => eprintln!("Warning: ignoring {}: {}", path.to_string_lossy(), err),
+        }
+    }
+    Ok(ld_cache::LdCache::default())
+}
+
+/// Exit with the given status, reporting the timings first if requested
+fn exit(args: &options::Options, code: i32) -> ! {
+    if args.timings {
+        timings::report();
+    }
+    std::process::exit(code)
+}
+
 fn main() -> anyhow::Result<()> {
     let args = options::Options::parse();
-    let summary = summarize::summarize_path(&args.input)?;
-    let search_path = search_path::search_path(&args.sysroot, &summary);
-    let deps = dependencies::resolve_dependencies(&search_path, &summary);
+    let log_level = if args.verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Warn
+    };
+    env_logger::Builder::new()
+        .filter_level(log_level)
+        .format_timestamp(None)
+        .init();
+    // Each line is flushed as it is written, so nothing is lost if we exit
+    // early (e.g., when a check fails)
+    let mut out: Box<dyn Write> = match &args.output {
+        None => Box::new(io::stdout()),
+        Some(path) => Box::new(io::LineWriter::new(fs::File::create(path)?)),
+    };
+
+    // Inputs that cannot be summarized are skipped (with a warning) so that
+    // one bad file does not abort a batch
+    let export_types: Vec<summarize::SymbolType> = args
+        .exports_of_type
+        .iter()
+        .map(|t| t.symbol_type())
+        .collect();
 
-    if args.interactive {
-        let dur = Duration::from_millis(250);
-        return ui::crossterm::run(dur, &summary, &deps);
+    let mut summaries = Vec::new();
+    let mut any_input_failed = false;
+    for input in args.input_paths()? {
+        match timings::time(timings::Phase::Summarize, || {
+            summarize::summarize_path(&input)
+        }) {
+            Ok(mut summary) => {
+                merge_debug_symbols(&mut summary, &args.debug_dir);
+                if !args.dynsym_order {
+                    summary.sort_symbols();
+                }
+                if args.sort_by_address {
+                    summary.sort_symbols_by_address();
+                }
+                summaries.push(summary);
+            }
+            Err(err) => {
+                eprintln!("Warning: skipping {}: {}", input.to_string_lossy(), err);
+                any_input_failed = true;
+            }
+        }
+    }
+
+    if let Some(expected) = &args.expect_arch {
+        let mut all_match = true;
+        for summary in &summaries {
+            all_match &= check_architecture(summary, expected);
+        }
+        if !all_match {
+            exit(&args, EXIT_CHECK_FAILED);
+        }
+    }
+
+    let ld_cache = load_ld_cache(&args)?;
+
+    if args.security {
+        if !args.require.is_empty() {
+            let mut all_passed = true;
+            for summary in &summaries {
+                all_passed &= check_security_requirements(summary, &args.require);
+            }
+            if !all_passed {
+                exit(&args, EXIT_CHECK_FAILED);
+            }
+        } else {
+            let summary_refs: Vec<&summarize::ElfSummary> = summaries.iter().collect();
+            render_security(&mut out, &summary_refs, args.format)?;
+        }
+    } else if let Some(section_name) = &args.dump_section {
+        for summary in &summaries {
+            match summarize::read_section(&summary.filename, section_name)? {
+                None => {
+                    eprintln!(
+                        "Warning: {} has no section named {}",
+                        summary.filename.to_string_lossy(),
+                        section_name
+                    );
+                    any_input_failed = true;
+                }
+                Some(contents) => {
+                    writeln!(
+                        out,
+                        "Contents of section {} in {}:",
+                        section_name,
+                        summary.filename.to_string_lossy()
+                    )?;
+                    render_hexdump(&mut out, &contents)?;
+                }
+            }
+        }
+    } else if args.strings {
+        for summary in &summaries {
+            let strings = summarize::read_strings(&summary.filename)?;
+            render_strings(&mut out, &strings, args.format)?;
+        }
+    } else if let Some(allowlist_path) = &args.allowed_symbols {
+        let allowed = allowlist::Allowlist::read(allowlist_path)?;
+        let mut all_passed = true;
+        for summary in &summaries {
+            all_passed &= check_allowed_symbols(summary, &allowed);
+        }
+        if !all_passed {
+            exit(&args, EXIT_CHECK_FAILED);
+        }
+    } else if let Some(provider_path) = &args.check_provider {
+        let provider = summarize::summarize_path(provider_path)?;
+        let reports: Vec<_> = summaries
+            .iter()
+            .map(|summary| resolve_symbols::satisfies_references(&provider, summary))
+            .collect();
+        render_satisfaction(&mut out, &reports, args.format)?;
+        if reports.iter().any(|r| !r.unsatisfied.is_empty()) {
+            exit(&args, EXIT_CHECK_FAILED);
+        }
+    } else if args.interactive {
+        if summaries.len() != 1 {
+            anyhow::bail!("The interactive UI requires exactly one input file");
+        }
+        let input = summaries[0].filename.clone();
+        let load = || -> anyhow::Result<ui::crossterm::LoadedBinaries> {
+            let mut summary = summarize::summarize_path(&input)?;
+            merge_debug_symbols(&mut summary, &args.debug_dir);
+            if !args.dynsym_order {
+                summary.sort_symbols();
+            }
+            if args.sort_by_address {
+                summary.sort_symbols_by_address();
+            }
+            let resolution = dependencies::resolve_dependencies(
+                &args.sysroot,
+                &ld_cache,
+                &summary,
+                args.system_only,
+                &args.ignore_dep,
+                args.max_depth,
+            );
+            Ok((summary, resolution))
+        };
+        let dur = Duration::from_millis(args.tick_rate);
+        return ui::crossterm::run(
+            dur,
+            args.watch,
+            !args.no_mouse,
+            &ui::app::SymbolTableOptions {
+                columns: args.columns.clone(),
+                human_sizes: args.human,
+                demangle_style: args.demangle_style,
+                export_types: export_types.clone(),
+            },
+            ui::theme::Theme::new(args.color),
+            load,
+        );
+    } else {
+        let mut json_reports = Vec::new();
+        let mut bom = sbom::Bom::default();
+        let mut unresolved_items = Vec::new();
+        let mut count_reports = Vec::new();
+        let mut locked_binaries = Vec::new();
+        let locked = match &args.verify {
+            None => None,
+            Some(path) => Some(lockfile::read(path)?),
+        };
+        let baseline = match &args.abi_baseline {
+            None => None,
+            Some(path) => Some(abi_baseline::read(path)?),
+        };
+        let preloads = args
+            .preload
+            .iter()
+            .map(summarize::summarize_path)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut all_verified = true;
+        let mut abi_unchanged = true;
+        let mut all_in_sysroot = true;
+        for summary in &summaries {
+            if let Some(count) = args.top_symbols {
+                render_top_symbols(
+                    &mut out,
+                    summary,
+                    count,
+                    args.demangle_style,
+                    args.human,
+                    args.format,
+                )?;
+                continue;
+            }
+
+            let resolution = timings::time(timings::Phase::Dependencies, || {
+                dependencies::resolve_dependencies(
+                    &args.sysroot,
+                    &ld_cache,
+                    summary,
+                    args.system_only,
+                    &args.ignore_dep,
+                    args.max_depth,
+                )
+            });
+
+            let outside = dependencies::outside_sysroots(&resolution, &args.sysroot);
+            for dep in &outside {
+                eprintln!(
+                    "{}: {}: {}",
+                    if args.strict_sysroot {
+                        "Error"
+                    } else {
+                        "Warning"
+                    },
+                    summary.filename.to_string_lossy(),
+                    dep
+                );
+            }
+            all_in_sysroot &= outside.is_empty();
+
+            if args.lockfile {
+                locked_binaries.push(lockfile::lock_binary(summary, &resolution));
+                continue;
+            }
+
+            if let Some(locked) = &locked {
+                let drift = lockfile::verify(locked, &lockfile::lock_binary(summary, &resolution));
+                for d in &drift {
+                    eprintln!("{}: {}", summary.filename.to_string_lossy(), d);
+                }
+                all_verified &= drift.is_empty();
+                continue;
+            }
+
+            if let Some(baseline) = &baseline {
+                match abi_baseline::find(baseline, summary) {
+                    None => {
+                        eprintln!(
+                            "{}: not in the ABI baseline",
+                            summary.filename.to_string_lossy()
+                        );
+                        abi_unchanged = false;
+                    }
+                    Some(previous) => {
+                        let changes = abi_baseline::compare(previous, summary);
+                        render_abi_changes(&mut out, summary, &changes, args.format)?;
+                        abi_unchanged &= changes.is_empty();
+                    }
+                }
+                continue;
+            }
+
+            if args.count_only {
+                // This skips resolving symbols, which is most of the cost of a summary
+                count_reports.push(json::counts_report(summary, &resolution.dependencies));
+                continue;
+            }
+
+            if args.abi_versions {
+                let compat = dependencies::version_compatibility(summary, &resolution.dependencies);
+                render_version_compatibility(&mut out, summary, &compat, args.format)?;
+                continue;
+            }
+
+            if !preloads.is_empty() {
+                let refs: &[summarize::DynamicSymbolReference] = match &summary.binary_type {
+                    summarize::BinaryType::Static => &[],
+                    summarize::BinaryType::Dynamic(dyn_data) => &dyn_data.dynamic_symbol_refs,
+                };
+                let preload_refs: Vec<&summarize::ElfSummary> = preloads.iter().collect();
+                let all_libs: Vec<&summarize::ElfSummary> = resolution
+                    .dependencies
+                    .values()
+                    .filter_map(|x| x.as_ref())
+                    .collect();
+                let interposed = resolve_symbols::interpositions(refs, &preload_refs, &all_libs);
+                render_interpositions(&mut out, summary, &interposed, args.format)?;
+                continue;
+            }
+
+            if args.closure_size {
+                let size = dependencies::closure_size(summary, &resolution.dependencies);
+                render_closure_size(&mut out, &size, args.human, args.format)?;
+                continue;
+            }
+
+            if args.dependency_usage {
+                let all_libs: Vec<&summarize::ElfSummary> = resolution
+                    .dependencies
+                    .values()
+                    .filter_map(|x| x.as_ref())
+                    .collect();
+                let (resolutions, _) = resolve_root_symbols(summary, &all_libs);
+                let usage =
+                    dependencies::dependency_usage(summary, &resolution.dependencies, &resolutions);
+                render_dependency_usage(&mut out, summary, &usage, args.format)?;
+                continue;
+            }
+
+            if let Some(lib_name) = &args.needed_by {
+                let dependents =
+                    dependencies::needed_by(summary, &resolution.dependencies, lib_name);
+                render_needed_by(&mut out, lib_name, &dependents, args.format)?;
+                continue;
+            }
+
+            if let Some(symbol) = &args.explain_symbol {
+                render_explanation(&mut out, summary, &resolution, symbol, args.format)?;
+                continue;
+            }
+
+            if args.unresolved || args.only_missing {
+                // These are combined across all of the inputs so that there is
+                // a single list to check
+                unresolved_items.extend(dependencies::unresolved_items(summary, &resolution));
+                continue;
+            }
+
+            match args.format {
+                options::OutputFormat::Text => render_summary(
+                    &mut out,
+                    summary,
+                    &resolution,
+                    &args.columns,
+                    args.human,
+                    args.demangle_style,
+                    &export_types,
+                )?,
+                options::OutputFormat::TreeText => {
+                    render_dependency_tree(&mut out, summary, &resolution)?
+                }
+                options::OutputFormat::Oneline => render_oneline(&mut out, summary, &resolution)?,
+                options::OutputFormat::Nm => {
+                    render_nm(&mut out, summary, args.sort_by_address, summaries.len() > 1)?
+                }
+                options::OutputFormat::Json => {
+                    json_reports.push(summary_json_report(summary, &resolution))
+                }
+                options::OutputFormat::Sbom => bom.add(summary, &resolution),
+            }
+        }
+
+        if args.unresolved || args.only_missing {
+            render_unresolved(&mut out, &unresolved_items, args.format)?;
+        }
+        if args.only_missing && !unresolved_items.is_empty() {
+            eprintln!(
+                "{} unresolved dependencies or symbols",
+                unresolved_items.len()
+            );
+            exit(&args, EXIT_CHECK_FAILED);
+        }
+
+        if args.count_only {
+            render_counts(&mut out, count_reports, args.format)?;
+        }
+
+        if args.lockfile {
+            writeln!(out, "{}", serde_json::to_string_pretty(&locked_binaries)?)?;
+        }
+
+        // The reports are written before exiting for a failed check, just
+        // like the text output (which is written as each input is processed)
+        if !json_reports.is_empty() {
+            writeln!(out, "{}", serde_json::to_string_pretty(&json_reports)?)?;
+        }
+        if !bom.components.is_empty() {
+            writeln!(out, "{}", serde_json::to_string_pretty(&bom)?)?;
+        }
+
+        if !all_verified || !abi_unchanged || (args.strict_sysroot && !all_in_sysroot) {
+            exit(&args, EXIT_CHECK_FAILED);
+        }
     }
 
-    render_summary(&summary, &deps)?;
+    if any_input_failed {
+        exit(&args, EXIT_INPUT_FAILED);
+    }
+    if args.timings {
+        timings::report();
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Render the text summary of one of the binaries in `tests/fixtures`,
+    /// resolving its dependencies the same way as `main`
+    ///
+    /// The canonical path of the fixtures directory depends on where the
+    /// repository is checked out, so it is replaced with `$FIXTURES`
+    fn render_fixture(name: &str) -> String {
+        let fixtures = Path::new("tests/fixtures");
+        let mut summary = summarize::summarize_path(&fixtures.join(name)).unwrap();
+        summary.sort_symbols();
+        // The cache of the host is not used, so that the results do not depend on it
+        let resolution = dependencies::resolve_dependencies(
+            &[PathBuf::from("/")],
+            &ld_cache::LdCache::default(),
+            &summary,
+            false,
+            &[],
+            None,
+        );
+        let mut out = Vec::new();
+        render_summary(
+            &mut out,
+            &summary,
+            &resolution,
+            &[],
+            false,
+            options::DemangleStyle::Full,
+            &[],
+        )
+        .unwrap();
+        let canonical = fs::canonicalize(fixtures).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .replace(canonical.to_string_lossy().as_ref(), "$FIXTURES")
+    }
+
+    /// Compare the summary of a fixture with its golden file in `tests/golden`
+    /// (or overwrite the golden file if `UPDATE_GOLDEN` is set)
+    fn check_golden(name: &str) {
+        let actual = render_fixture(name);
+        let golden = Path::new("tests/golden").join(format!("{}.txt", name));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&golden, actual).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&golden).unwrap();
+        assert_eq!(
+            actual,
+            expected,
+            "The summary of {} does not match {}",
+            name,
+            golden.display()
+        );
+    }
+
+    #[test]
+    fn golden_static_executable() {
+        check_golden("static");
+    }
+
+    #[test]
+    fn golden_dynamic_executable() {
+        check_golden("dynamic");
+    }
+
+    #[test]
+    fn golden_shared_library() {
+        check_golden("libfixture.so");
+    }
+}
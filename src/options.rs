@@ -1,17 +1,415 @@
 use clap::Parser;
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use crate::proc_maps;
+use crate::summarize::SymbolType;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables
+    Text,
+    /// Structured JSON
+    Json,
+    /// A single line per binary (or per item), for grepping
+    Oneline,
+    /// The dependency tree of each binary, drawn with box-drawing characters
+    /// (other reports use the text format)
+    TreeText,
+    /// The dynamic symbols of each binary, like `nm -D` (other reports use the
+    /// text format)
+    Nm,
+    /// A CycloneDX-style bill of materials listing the inputs and the resolved
+    /// libraries in their closures, with their hashes and build IDs (other
+    /// reports use the text format)
+    Sbom,
+}
+
+/// A column of the symbol tables, for `--columns`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SymbolColumn {
+    Addr,
+    Size,
+    Type,
+    Binding,
+    /// The symbol name (including the version, unless `version` is also selected)
+    Name,
+    /// The demangled symbol name (per `--demangle-style`), next to the raw
+    /// `name` if both are selected
+    Demangled,
+    Version,
+    Visibility,
+    /// The section containing a defined symbol (e.g., `.text` or `.data`)
+    Section,
+    /// The binary providing a referenced symbol
+    Provider,
+}
+
+/// A type of exported symbol, for `--exports-of-type`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportType {
+    Func,
+    Object,
+    Tls,
+    Ifunc,
+}
+
+impl ExportType {
+    pub fn symbol_type(self) -> SymbolType {
+        match self {
+            ExportType::Func => SymbolType::Func,
+            ExportType::Object => SymbolType::Object,
+            ExportType::Tls => SymbolType::Tls,
+            ExportType::Ifunc => SymbolType::Ifunc,
+        }
+    }
+}
+
+/// How much detail to include in demangled names, for `--demangle-style`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DemangleStyle {
+    /// Include C++ parameter and return types, and the hash of Rust names
+    Full,
+    /// Only the (qualified) names of functions
+    Short,
+}
+
+/// The colors supported by the terminal, for `--color`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorMode {
+    /// Detect the supported colors from `COLORTERM` and `TERM`
+    Auto,
+    /// The 16 standard ANSI colors
+    #[clap(name = "16")]
+    Ansi16,
+    /// The 256 color palette
+    #[clap(name = "256")]
+    Ansi256,
+    /// 24 bit color
+    Truecolor,
+}
+
+/// A hardening property that can be required with `--require`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SecurityRequirement {
+    Nx,
+    Pie,
+    /// At least partial RELRO
+    Relro,
+    RelroFull,
+    Canary,
+    Fortify,
+    /// CET indirect branch tracking
+    Ibt,
+    /// CET shadow stacks
+    Shstk,
+    Bti,
+    /// No text relocations
+    NoTextrel,
+    /// No insecure `DT_RPATH`/`DT_RUNPATH` entries
+    NoInsecureRpath,
+}
 
 #[derive(Debug, Parser)]
 #[clap(version = "1.0", author = "Tristan Ravitch")]
 pub struct Options {
-    #[clap(help = "The file to examine")]
-    pub input: PathBuf,
+    #[clap(help = "The files to examine")]
+    pub input: Vec<PathBuf>,
+    #[clap(
+        help = "Read additional newline-separated input paths from a file (or `-` for stdin)",
+        long = "files-from",
+        value_name = "PATH"
+    )]
+    pub files_from: Option<PathBuf>,
     #[clap(
-        help = "The system root to use to search for dependencies",
+        help = "Examine the executable and shared libraries currently mapped into the process with this ID (Linux only)",
+        long = "pid"
+    )]
+    pub pid: Option<u32>,
+    #[clap(
+        help = "The system root to use to search for dependencies; repeat to search multiple roots (e.g., image layers) in order",
         long = "sysroot",
         default_value = "/"
     )]
-    pub sysroot: PathBuf,
+    pub sysroot: Vec<PathBuf>,
+    #[clap(
+        help = "The ld.so.cache file to look up libraries in (by default, etc/ld.so.cache under the first sysroot that has one)",
+        long = "ld-cache",
+        value_name = "PATH"
+    )]
+    pub ld_cache: Option<PathBuf>,
+    #[clap(
+        help = "Fail (with exit status 3) if a dependency resolves to a file outside of the sysroots, rather than only warning about it",
+        long = "strict-sysroot"
+    )]
+    pub strict_sysroot: bool,
+    #[clap(
+        help = "Fail (with exit status 3) unless every input is built for this architecture (e.g., x86_64 or aarch64)",
+        long = "expect-arch",
+        value_name = "ARCH"
+    )]
+    pub expect_arch: Option<String>,
+    #[clap(
+        help = "Only resolve dependencies found in the default system library directories (ignoring RPATH, RUNPATH, and LD_LIBRARY_PATH)",
+        long = "system-only"
+    )]
+    pub system_only: bool,
+    #[clap(
+        help = "Do not search for the named library (e.g., because it is provided by the host or loaded with dlopen); can be repeated",
+        long = "ignore-dep",
+        value_name = "NAME"
+    )]
+    pub ignore_dep: Vec<String>,
+    #[clap(
+        help = "Only search for dependencies up to N levels below the input (1 is only the direct dependencies); the libraries that are cut off are listed as not explored",
+        long = "max-depth",
+        alias = "depth",
+        value_name = "N"
+    )]
+    pub max_depth: Option<usize>,
+    #[clap(
+        help = "Read the symbols of stripped inputs from their separate debug files, found by build ID or `.gnu_debuglink` under this directory (e.g., /usr/lib/debug); can be repeated",
+        long = "debug-dir",
+        value_name = "DIR"
+    )]
+    pub debug_dir: Vec<PathBuf>,
+    #[clap(
+        help = "Log each library candidate tried while resolving dependencies (and why it was rejected) to stderr",
+        long = "verbose",
+        short = 'v',
+        conflicts_with = "interactive"
+    )]
+    pub verbose: bool,
+    #[clap(
+        help = "Print the time spent summarizing the inputs, resolving dependencies, and resolving symbols (and the number of files parsed) to stderr",
+        long = "timings",
+        conflicts_with = "interactive"
+    )]
+    pub timings: bool,
     #[clap(help = "Start the interactive UI", long = "interactive", short = 'i')]
     pub interactive: bool,
+    #[clap(
+        help = "Refresh the interactive UI when the binary or its dependencies change on disk",
+        long = "watch",
+        requires = "interactive"
+    )]
+    pub watch: bool,
+    #[clap(
+        help = "How often (in milliseconds) the interactive UI refreshes when idle",
+        long = "tick-rate",
+        value_name = "MS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        default_value = "250"
+    )]
+    pub tick_rate: u64,
+    #[clap(
+        help = "Do not capture the mouse in the interactive UI (allows terminal text selection)",
+        long = "no-mouse"
+    )]
+    pub no_mouse: bool,
+    #[clap(
+        help = "The colors the terminal supports, which determines the palette of the interactive UI",
+        long = "color",
+        value_enum,
+        default_value = "auto"
+    )]
+    pub color: ColorMode,
+    #[clap(
+        help = "The columns of the symbol tables to show, in order (in both the text output and the interactive UI)",
+        long = "columns",
+        value_enum,
+        value_delimiter = ','
+    )]
+    pub columns: Vec<SymbolColumn>,
+    #[clap(
+        help = "Only show the defined symbols of these types (comma separated); in the interactive UI, this is the initial state of the filter toggled with `t`",
+        long = "exports-of-type",
+        value_enum,
+        value_delimiter = ','
+    )]
+    pub exports_of_type: Vec<ExportType>,
+    #[clap(
+        help = "Show symbol sizes in human-readable units (e.g., 1.2K) in the symbol tables",
+        long = "human"
+    )]
+    pub human: bool,
+    #[clap(
+        help = "List dynamic symbols in the order of the dynamic symbol table, rather than sorted by name",
+        long = "dynsym-order"
+    )]
+    pub dynsym_order: bool,
+    #[clap(
+        help = "Sort the defined symbols by address, listing the undefined references separately (after them)",
+        long = "sort-by-address"
+    )]
+    pub sort_by_address: bool,
+    #[clap(
+        help = "Report the hardening properties of the binary (checksec-style)",
+        long = "security"
+    )]
+    pub security: bool,
+    #[clap(
+        help = "The output format",
+        long = "format",
+        value_enum,
+        default_value = "text"
+    )]
+    pub format: OutputFormat,
+    #[clap(
+        help = "Write the results to this file instead of stdout (warnings are still printed to stderr)",
+        long = "output",
+        short = 'o',
+        value_name = "PATH",
+        conflicts_with = "interactive"
+    )]
+    pub output: Option<PathBuf>,
+    #[clap(
+        help = "Print nothing and exit with status 3 if the binary lacks any of these hardening properties",
+        long = "require",
+        value_enum,
+        value_delimiter = ',',
+        requires = "security"
+    )]
+    pub require: Vec<SecurityRequirement>,
+    #[clap(
+        help = "Print a hexdump of the contents of the named section (decompressing it if needed)",
+        long = "dump-section",
+        value_name = "NAME"
+    )]
+    pub dump_section: Option<String>,
+    #[clap(
+        help = "Print the strings in the dynamic string table, the `.comment` section, and the note descriptors",
+        long = "strings"
+    )]
+    pub strings: bool,
+    #[clap(
+        help = "Report every referenced dynamic symbol that is not listed (as `name[@version]`) in this file, and exit with status 3 if there are any",
+        long = "allowed-symbols",
+        value_name = "FILE"
+    )]
+    pub allowed_symbols: Option<PathBuf>,
+    #[clap(
+        help = "Check which of the symbols referenced by each input are defined (with the required version) by the given library, exiting with status 3 if any are not",
+        long = "check-provider",
+        value_name = "LIBRARY"
+    )]
+    pub check_provider: Option<PathBuf>,
+    #[clap(
+        help = "List the symbols referenced by the binary that this library would interpose if it were preloaded (e.g., with LD_PRELOAD); can be repeated",
+        long = "preload",
+        value_name = "LIBRARY"
+    )]
+    pub preload: Vec<PathBuf>,
+    #[clap(
+        help = "List the modules in the dependency closure that directly require the named library",
+        long = "needed-by",
+        value_name = "LIBNAME"
+    )]
+    pub needed_by: Option<String>,
+    #[clap(
+        help = "Compare the symbol versions required from each direct dependency with the versions it provides",
+        long = "abi-versions"
+    )]
+    pub abi_versions: bool,
+    #[clap(
+        help = "Report the combined size on disk of the binary and every resolved dependency, listing the largest first",
+        long = "closure-size"
+    )]
+    pub closure_size: bool,
+    #[clap(
+        help = "Report how many of the binary's symbol references each of its direct dependencies satisfies, most used first",
+        long = "dependency-usage"
+    )]
+    pub dependency_usage: bool,
+    #[clap(
+        help = "List every unresolved dependency and symbol in the dependency closure",
+        long = "unresolved"
+    )]
+    pub unresolved: bool,
+    #[clap(
+        help = "Like --unresolved, but exit with status 3 if anything is unresolved (for use as a pass/fail check)",
+        long = "only-missing"
+    )]
+    pub only_missing: bool,
+    #[clap(
+        help = "Explain how references to the named symbol (optionally `name@version`) are resolved",
+        long = "explain-symbol",
+        value_name = "SYMBOL"
+    )]
+    pub explain_symbol: Option<String>,
+    #[clap(
+        help = "List the N largest dynamic symbols defined by each binary",
+        long = "top-symbols",
+        value_name = "N"
+    )]
+    pub top_symbols: Option<usize>,
+    #[clap(
+        help = "Only print the number of dependencies, unresolved dependencies, and defined and referenced symbols of each binary (and the totals)",
+        long = "count-only"
+    )]
+    pub count_only: bool,
+    #[clap(
+        help = "Print a lockfile (JSON) recording the path, build ID, and SONAME of each dependency in the closure",
+        long = "lockfile"
+    )]
+    pub lockfile: bool,
+    #[clap(
+        help = "Check that the dependencies still resolve to the same paths and build IDs as recorded in a lockfile",
+        long = "verify",
+        value_name = "LOCKFILE",
+        conflicts_with = "lockfile"
+    )]
+    pub verify: Option<PathBuf>,
+    #[clap(
+        help = "Report the dynamic symbols that were added, removed, or changed in type, binding, or size relative to a previous JSON summary (from --format json)",
+        long = "abi-baseline",
+        value_name = "JSON"
+    )]
+    pub abi_baseline: Option<PathBuf>,
+    #[clap(
+        help = "How much detail to include in demangled C++ and Rust names",
+        long = "demangle-style",
+        value_enum,
+        default_value = "full"
+    )]
+    pub demangle_style: DemangleStyle,
+}
+
+fn read_path_list<R: BufRead>(reader: R) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            paths.push(PathBuf::from(trimmed));
+        }
+    }
+    Ok(paths)
+}
+
+impl Options {
+    /// All of the inputs to examine, including those listed in `--files-from`
+    /// and those mapped into the process named by `--pid`
+    pub fn input_paths(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut paths = self.input.clone();
+        match &self.files_from {
+            None => {}
+            Some(list) if list.as_path() == Path::new("-") => {
+                paths.append(&mut read_path_list(io::stdin().lock())?);
+            }
+            Some(list) => {
+                let f = fs::File::open(list)?;
+                paths.append(&mut read_path_list(io::BufReader::new(f))?);
+            }
+        }
+        if let Some(pid) = self.pid {
+            paths.append(&mut proc_maps::mapped_binaries(pid)?);
+        }
+
+        if paths.is_empty() {
+            anyhow::bail!("No input files given");
+        }
+        Ok(paths)
+    }
 }
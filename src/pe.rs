@@ -0,0 +1,138 @@
+//! Summaries of Windows PE binaries (executables and DLLs)
+//!
+//! PE files are mapped onto the same summary as ELF binaries so that the rest
+//! of the tool works on them unchanged: the imported DLLs are the
+//! dependencies, the imported functions are the symbol references, and the
+//! exported functions are the provided symbols.  The ELF-specific details
+//! (e.g., search paths, symbol versions, and hash tables) are left empty.
+
+use object::{elf, Architecture, Endianness, Object, ObjectKind, ObjectSection};
+use std::path::{Path, PathBuf};
+
+use crate::summarize::{
+    BinaryType, DynamicData, DynamicSymbolReference, ElfSummary, ExportedDynamicSymbol,
+    RelocationCounts, Section, SymbolBinding, SymbolSection, SymbolType, SymbolVisibility,
+    VersionedSymbol,
+};
+
+/// The ELF machine corresponding to the architecture of a PE file (so that
+/// machines are named the same way for both formats)
+fn elf_machine(arch: Architecture) -> u16 {
+    match arch {
+        Architecture::I386 => elf::EM_386,
+        Architecture::X86_64 => elf::EM_X86_64,
+        Architecture::Arm => elf::EM_ARM,
+        Architecture::Aarch64 => elf::EM_AARCH64,
+        _ => elf::EM_NONE,
+    }
+}
+
+fn function_symbol(name: &[u8]) -> VersionedSymbol {
+    VersionedSymbol {
+        name: String::from_utf8_lossy(name).to_string(),
+        version: None,
+    }
+}
+
+/// Summarize a PE file (which must have already been identified as one)
+pub fn summarize_pe(path: &Path, bytes: &[u8]) -> anyhow::Result<ElfSummary> {
+    let file = object::File::parse(bytes)?;
+
+    let mut deps: Vec<String> = Vec::new();
+    let mut refs = Vec::new();
+    for import in file.imports()? {
+        let dll = String::from_utf8_lossy(import.library()).to_string();
+        if !deps.contains(&dll) {
+            deps.push(dll);
+        }
+        refs.push(DynamicSymbolReference {
+            symbol: function_symbol(import.name()),
+            type_: SymbolType::Func,
+            binding: SymbolBinding::Global,
+            visibility: SymbolVisibility::Default,
+        });
+    }
+
+    let sections: Vec<Section> = file
+        .sections()
+        .map(|sec| Section {
+            name: sec.name().unwrap_or("<Unknown>").to_string(),
+            address: sec.address(),
+            alignment: sec.align(),
+            offset: sec.file_range().map_or(0, |(offset, _)| offset),
+            size: sec.size(),
+            type_: elf::SHT_PROGBITS,
+            flags: u64::from(elf::SHF_ALLOC),
+        })
+        .collect();
+
+    let exports: Vec<ExportedDynamicSymbol> = file
+        .exports()?
+        .iter()
+        .map(|export| ExportedDynamicSymbol {
+            symbol: function_symbol(export.name()),
+            type_: SymbolType::Func,
+            binding: SymbolBinding::Global,
+            visibility: SymbolVisibility::Default,
+            size: 0,
+            address: export.address(),
+            // PE sections are not numbered like ELF sections, so this is the
+            // position of the section in `sections`
+            section: sections
+                .iter()
+                .position(|sec| sec.contains_address(export.address()))
+                .map_or(SymbolSection::Absolute, SymbolSection::Section),
+        })
+        .collect();
+
+    let dyn_data = DynamicData {
+        dynamic_symbol_count: refs.len() + exports.len(),
+        dynamic_symbol_refs: refs,
+        provided_dynamic_symbols: exports,
+        deps,
+        soname: None,
+        rpath: Vec::new(),
+        runpath: Vec::new(),
+        filters: Vec::new(),
+        auxiliary_filters: Vec::new(),
+        audit: Vec::new(),
+        dependency_audit: Vec::new(),
+        flags: 0,
+        flags_1: 0,
+        bind_now: false,
+        textrel: false,
+        version_definitions: Vec::new(),
+        version_requirements: Vec::new(),
+        init: None,
+        fini: None,
+        init_array: Vec::new(),
+        fini_array: Vec::new(),
+        hash_symbol_counts: Vec::new(),
+        relocation_counts: RelocationCounts::default(),
+    };
+
+    Ok(ElfSummary {
+        endianness: Endianness::Little,
+        bit_size: if file.is_64() { 64 } else { 32 },
+        filename: PathBuf::from(path),
+        file_type: match file.kind() {
+            ObjectKind::Dynamic => elf::ET_DYN,
+            _ => elf::ET_EXEC,
+        },
+        machine: elf_machine(file.architecture()),
+        entry: file.entry(),
+        binary_type: BinaryType::Dynamic(dyn_data),
+        sections,
+        segments: Vec::new(),
+        feature_1_and: None,
+        comment: Vec::new(),
+        build_id: None,
+        debug_link: None,
+        debug_file: None,
+        debug_symbols: Vec::new(),
+        debug_sections: Vec::new(),
+        sha256: String::new(),
+        core: None,
+        modified: None,
+    })
+}
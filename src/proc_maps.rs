@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Parse the contents of a `/proc/<pid>/maps` file into the paths of the
+/// executable and shared libraries mapped into the process, in address order
+///
+/// Each line has the form `start-end perms offset dev inode [path]`.  Only
+/// executable mappings of files are considered, which excludes data files
+/// (e.g., locale archives) as well as anonymous and special mappings (e.g.,
+/// `[vdso]`).  Each file is listed once, even though it usually has several
+/// mappings.
+fn parse_maps(contents: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for line in contents.lines() {
+        let mut fields = line.splitn(6, ' ');
+        let perms = fields.nth(1).unwrap_or("");
+        let path = fields.nth(3).unwrap_or("").trim_start();
+        if !perms.contains('x') || !path.starts_with('/') {
+            continue;
+        }
+        // Files that were replaced after being mapped cannot be examined
+        if path.ends_with(" (deleted)") {
+            log::warn!("Skipping deleted mapped file {}", path);
+            continue;
+        }
+        let path = PathBuf::from(path);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// The binaries (the executable and its shared libraries) currently mapped into the process `pid`
+pub fn mapped_binaries(pid: u32) -> anyhow::Result<Vec<PathBuf>> {
+    let maps = fs::read_to_string(format!("/proc/{}/maps", pid))?;
+    Ok(parse_maps(&maps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "\
+55d4c6a00000-55d4c6a02000 r--p 00000000 fd:01 1835083                    /usr/bin/cat
+55d4c6a02000-55d4c6a07000 r-xp 00002000 fd:01 1835083                    /usr/bin/cat
+55d4c6a07000-55d4c6a0a000 r--p 00007000 fd:01 1835083                    /usr/bin/cat
+55d4c7f2e000-55d4c7f4f000 rw-p 00000000 00:00 0                          [heap]
+7f3b2a000000-7f3b2a2e9000 r--p 00000000 fd:01 1836528                    /usr/lib/locale/locale-archive
+7f3b2a400000-7f3b2a428000 r--p 00000000 fd:01 1840913                    /usr/lib/x86_64-linux-gnu/libc.so.6
+7f3b2a428000-7f3b2a5bd000 r-xp 00028000 fd:01 1840913                    /usr/lib/x86_64-linux-gnu/libc.so.6
+7f3b2a600000-7f3b2a610000 r-xp 00000000 fd:01 1840999                    /opt/lib/libold.so (deleted)
+7f3b2a7f0000-7f3b2a7f3000 rw-p 00000000 00:00 0
+7f3b2a81b000-7f3b2a81f000 r--p 00000000 00:00 0                          [vvar]
+7f3b2a81f000-7f3b2a821000 r-xp 00000000 00:00 0                          [vdso]
+7f3b2a821000-7f3b2a847000 r-xp 00001000 fd:01 1840907                    /usr/lib/x86_64-linux-gnu/ld-linux-x86-64.so.2
+";
+
+    #[test]
+    fn executable_file_mappings_are_listed_once_in_order() {
+        assert_eq!(
+            parse_maps(MAPS),
+            vec![
+                PathBuf::from("/usr/bin/cat"),
+                PathBuf::from("/usr/lib/x86_64-linux-gnu/libc.so.6"),
+                PathBuf::from("/usr/lib/x86_64-linux-gnu/ld-linux-x86-64.so.2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn paths_with_spaces_are_kept_whole() {
+        let maps = "7f3b2a428000-7f3b2a5bd000 r-xp 00028000 fd:01 1840913                    /opt/my app/lib foo.so\n";
+        assert_eq!(
+            parse_maps(maps),
+            vec![PathBuf::from("/opt/my app/lib foo.so")]
+        );
+    }
+}
@@ -1,34 +1,297 @@
+use serde::Serialize;
 use std::collections;
+use std::path::PathBuf;
 
-use crate::summarize::{BinaryType, DynamicSymbolReference, ElfSummary, VersionedSymbol};
+use crate::summarize::{
+    BinaryType, DynamicSymbolReference, ElfSummary, SymbolBinding, VersionedSymbol,
+};
+
+/// Returns true if the definition `def` can satisfy a reference to `req`
+///
+/// An unversioned reference can be satisfied by any version of the symbol,
+/// while a versioned reference requires exactly that version.
+pub fn satisfies(def: &VersionedSymbol, req: &VersionedSymbol) -> bool {
+    def.name == req.name && (req.version.is_none() || def.version == req.version)
+}
+
+/// Index the symbols defined by each dependency by name
+fn index_definitions<'a>(
+    deps: &[&'a ElfSummary],
+) -> collections::HashMap<&'a str, Vec<(&'a VersionedSymbol, &'a ElfSummary)>> {
+    let mut defs: collections::HashMap<&str, Vec<_>> = collections::HashMap::new();
+    for &dep in deps {
+        match &dep.binary_type {
+            BinaryType::Static => {}
+            BinaryType::Dynamic(dyn_data) => {
+                for defined_sym in &dyn_data.provided_dynamic_symbols {
+                    defs.entry(defined_sym.symbol.name.as_str())
+                        .or_default()
+                        .push((&defined_sym.symbol, dep));
+                }
+            }
+        }
+    }
+    defs
+}
 
 /// Determine which dependencies provide each dynamic symbol referenced by the given `ElfSummary`
+///
+/// The resulting map is keyed by the (versioned) reference; if multiple
+/// dependencies provide a symbol, the first in `deps` wins.
 pub fn resolve_symbols<'a>(
-    dyn_sym_refs: &Vec<DynamicSymbolReference>,
-    deps: &Vec<&'a ElfSummary>,
+    dyn_sym_refs: &[DynamicSymbolReference],
+    deps: &[&'a ElfSummary],
 ) -> collections::BTreeMap<VersionedSymbol, &'a ElfSummary> {
     let mut res = collections::BTreeMap::new();
-    let mut needed_syms = collections::HashSet::new();
+    let defs = index_definitions(deps);
 
     for dyn_sym in dyn_sym_refs {
-        needed_syms.insert(&dyn_sym.symbol.name);
+        let provider = defs
+            .get(dyn_sym.symbol.name.as_str())
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|(def, _)| satisfies(def, &dyn_sym.symbol))
+            });
+        if let Some((_, elf)) = provider {
+            res.insert(dyn_sym.symbol.clone(), *elf);
+        }
     }
 
-    for dep in deps {
-        match &dep.binary_type {
-            BinaryType::Static => {}
-            BinaryType::Dynamic(dyn_data) => {
-                for defined_sym in &dyn_data.provided_dynamic_symbols {
-                    match needed_syms.get(&defined_sym.symbol.name) {
-                        None => {}
-                        Some(_) => {
-                            res.insert(defined_sym.symbol.clone(), *dep);
-                        }
-                    }
-                }
+    res
+}
+
+/// The reason that a symbol reference could not be resolved
+pub enum UnresolvedReason {
+    /// No dependency defines the symbol at all
+    Missing,
+    /// The symbol is defined, but not with the required version
+    ///
+    /// This records the versions that *are* available; the loader would reject
+    /// this binding at runtime
+    VersionMismatch(Vec<String>),
+}
+
+impl std::fmt::Display for UnresolvedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnresolvedReason::Missing => write!(f, "<Unresolved>"),
+            UnresolvedReason::VersionMismatch(available) => {
+                write!(f, "<Version missing; found {}>", available.join(", "))
             }
         }
     }
+}
+
+/// Explain why each reference missing from `resolutions` could not be resolved
+///
+/// This distinguishes symbols that are absent entirely from symbols that are
+/// present in some dependency, but only with a different version (i.e., version skew)
+pub fn diagnose_unresolved(
+    dyn_sym_refs: &[DynamicSymbolReference],
+    deps: &[&ElfSummary],
+    resolutions: &collections::BTreeMap<VersionedSymbol, &ElfSummary>,
+) -> collections::BTreeMap<VersionedSymbol, UnresolvedReason> {
+    let mut res = collections::BTreeMap::new();
+    let defs = index_definitions(deps);
+
+    for dyn_sym in dyn_sym_refs {
+        if resolutions.contains_key(&dyn_sym.symbol) {
+            continue;
+        }
 
+        let available: Vec<String> =
+            defs.get(dyn_sym.symbol.name.as_str())
+                .map_or(Vec::new(), |candidates| {
+                    candidates
+                        .iter()
+                        .map(|(def, _)| {
+                            def.version
+                                .clone()
+                                .unwrap_or_else(|| String::from("<None>"))
+                        })
+                        .collect()
+                });
+        let reason = if available.is_empty() {
+            UnresolvedReason::Missing
+        } else {
+            UnresolvedReason::VersionMismatch(available)
+        };
+        res.insert(dyn_sym.symbol.clone(), reason);
+    }
+
+    res
+}
+
+/// What the resolver concluded about one library while resolving a reference
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CandidateOutcome {
+    /// The library does not define the symbol
+    NotDefined,
+    /// The library defines the symbol, but not with the required version
+    VersionMismatch,
+    /// The library provides the symbol; it is the first in the search order
+    /// with a matching definition
+    Selected,
+    /// The library has a matching definition, but an earlier library was selected
+    Shadowed,
+}
+
+impl std::fmt::Display for CandidateOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CandidateOutcome::NotDefined => write!(f, "not defined"),
+            CandidateOutcome::VersionMismatch => write!(f, "no matching version"),
+            CandidateOutcome::Selected => {
+                write!(f, "selected (first matching definition in search order)")
+            }
+            CandidateOutcome::Shadowed => write!(f, "shadowed by an earlier library"),
+        }
+    }
+}
+
+/// A definition of the symbol being resolved
+#[derive(Serialize)]
+pub struct CandidateDefinition {
+    pub version: Option<String>,
+    pub binding: SymbolBinding,
+}
+
+/// A library considered while resolving a reference, and the resolver's conclusion about it
+#[derive(Serialize)]
+pub struct ResolutionCandidate {
+    pub library: PathBuf,
+    /// The library's definitions of the symbol (of any version)
+    pub definitions: Vec<CandidateDefinition>,
+    pub outcome: CandidateOutcome,
+}
+
+/// Explain how the reference `req` is resolved against `deps`
+///
+/// This makes the same decision as `resolve_symbols`, but records the outcome
+/// for every library in `deps` (in order).  Note that the binding of a
+/// definition does not affect the choice: like the loader, the first matching
+/// definition wins even if it is weak.
+pub fn explain_resolution(req: &VersionedSymbol, deps: &[&ElfSummary]) -> Vec<ResolutionCandidate> {
+    let mut selected = false;
+    let mut res = Vec::new();
+    for &dep in deps {
+        let definitions: Vec<_> = match &dep.binary_type {
+            BinaryType::Static => Vec::new(),
+            BinaryType::Dynamic(dyn_data) => dyn_data
+                .provided_dynamic_symbols
+                .iter()
+                .filter(|def| def.symbol.name == req.name)
+                .collect(),
+        };
+        let outcome = if definitions.is_empty() {
+            CandidateOutcome::NotDefined
+        } else if !definitions.iter().any(|def| satisfies(&def.symbol, req)) {
+            CandidateOutcome::VersionMismatch
+        } else if selected {
+            CandidateOutcome::Shadowed
+        } else {
+            selected = true;
+            CandidateOutcome::Selected
+        };
+        res.push(ResolutionCandidate {
+            library: dep.filename.clone(),
+            definitions: definitions
+                .iter()
+                .map(|def| CandidateDefinition {
+                    version: def.symbol.version.clone(),
+                    binding: def.binding,
+                })
+                .collect(),
+            outcome,
+        });
+    }
     res
 }
+
+/// A reference that a provider does not satisfy
+#[derive(Serialize)]
+pub struct UnsatisfiedReference {
+    /// The symbol, with its version (e.g., `memcpy@GLIBC_2.14`)
+    pub symbol: String,
+    /// The versions of the symbol that the provider does define (empty if it
+    /// does not define the symbol at all)
+    pub available_versions: Vec<String>,
+}
+
+/// Which of the references of a consumer are satisfied by a provider
+#[derive(Serialize)]
+pub struct SatisfactionReport {
+    pub provider: PathBuf,
+    pub consumer: PathBuf,
+    pub satisfied: Vec<String>,
+    pub unsatisfied: Vec<UnsatisfiedReference>,
+}
+
+/// Determine which of the dynamic symbol references of `consumer` are defined
+/// (with the required version) by `provider`
+///
+/// This answers whether `provider` could replace a library that `consumer`
+/// links against.  Note that every reference of `consumer` is checked, so
+/// references meant for its other dependencies are reported as unsatisfied.
+pub fn satisfies_references(provider: &ElfSummary, consumer: &ElfSummary) -> SatisfactionReport {
+    let refs: &[DynamicSymbolReference] = match &consumer.binary_type {
+        BinaryType::Static => &[],
+        BinaryType::Dynamic(dyn_data) => &dyn_data.dynamic_symbol_refs,
+    };
+    let deps = [provider];
+    let resolutions = resolve_symbols(refs, &deps);
+    let unresolved = diagnose_unresolved(refs, &deps, &resolutions);
+    SatisfactionReport {
+        provider: provider.filename.clone(),
+        consumer: consumer.filename.clone(),
+        satisfied: resolutions.keys().map(|sym| sym.to_string()).collect(),
+        unsatisfied: unresolved
+            .into_iter()
+            .map(|(symbol, reason)| UnsatisfiedReference {
+                symbol: symbol.to_string(),
+                available_versions: match reason {
+                    UnresolvedReason::Missing => Vec::new(),
+                    UnresolvedReason::VersionMismatch(available) => available,
+                },
+            })
+            .collect(),
+    }
+}
+
+/// A reference that a preloaded library would interpose
+#[derive(Serialize)]
+pub struct Interposition {
+    /// The symbol, with its version (e.g., `malloc@GLIBC_2.2.5`)
+    pub symbol: String,
+    /// The preloaded library that the reference would resolve to
+    pub preload: PathBuf,
+    /// The library the reference resolves to without the preloaded libraries
+    /// (or `None` if it is otherwise unresolved)
+    pub replaces: Option<PathBuf>,
+}
+
+/// Determine which of the references `dyn_sym_refs` would be interposed by
+/// the `preloads` (e.g., libraries in `LD_PRELOAD`)
+///
+/// Preloaded libraries come before all of the `deps` in the search order, so
+/// they take precedence for every symbol they define.
+pub fn interpositions(
+    dyn_sym_refs: &[DynamicSymbolReference],
+    preloads: &[&ElfSummary],
+    deps: &[&ElfSummary],
+) -> Vec<Interposition> {
+    let search_order: Vec<&ElfSummary> = preloads.iter().chain(deps).copied().collect();
+    let with_preloads = resolve_symbols(dyn_sym_refs, &search_order);
+    let without_preloads = resolve_symbols(dyn_sym_refs, deps);
+    with_preloads
+        .iter()
+        .filter(|(_, provider)| preloads.iter().any(|p| p.filename == provider.filename))
+        .map(|(sym, provider)| Interposition {
+            symbol: sym.to_string(),
+            preload: provider.filename.clone(),
+            replaces: without_preloads.get(sym).map(|p| p.filename.clone()),
+        })
+        .collect()
+}
@@ -0,0 +1,211 @@
+//! A minimal software bill of materials (in the style of CycloneDX) listing
+//! the inputs and the libraries in their dependency closures
+
+use serde::Serialize;
+use std::collections;
+use std::path::PathBuf;
+
+use crate::dependencies::Resolution;
+use crate::summarize::{BinaryType, ElfSummary};
+
+#[derive(Serialize)]
+pub struct Hash {
+    pub alg: &'static str,
+    pub content: String,
+}
+
+/// A named value that has no dedicated field in the format
+#[derive(Serialize)]
+pub struct Property {
+    pub name: &'static str,
+    pub value: String,
+}
+
+/// A binary in the bill of materials
+#[derive(Serialize)]
+pub struct Component {
+    /// `application` for the inputs and `library` for their dependencies
+    #[serde(rename = "type")]
+    pub type_: &'static str,
+    /// The path of the binary, which identifies it within the bill of materials
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: PathBuf,
+    /// The SONAME of the binary, or its file name if it has none
+    pub name: String,
+    pub hashes: Vec<Hash>,
+    /// The path and build ID of the binary
+    pub properties: Vec<Property>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bom {
+    pub bom_format: &'static str,
+    pub spec_version: &'static str,
+    pub version: u32,
+    pub components: Vec<Component>,
+}
+
+impl Default for Bom {
+    fn default() -> Self {
+        Bom {
+            bom_format: "CycloneDX",
+            spec_version: "1.4",
+            version: 1,
+            components: Vec::new(),
+        }
+    }
+}
+
+fn component(type_: &'static str, summ: &ElfSummary, fallback_name: &str) -> Component {
+    let soname = match &summ.binary_type {
+        BinaryType::Static => None,
+        BinaryType::Dynamic(dyn_data) => dyn_data.soname.clone(),
+    };
+    let mut properties = vec![Property {
+        name: "binary-walkr:path",
+        value: summ.filename.to_string_lossy().into_owned(),
+    }];
+    if let Some(build_id) = &summ.build_id {
+        properties.push(Property {
+            name: "binary-walkr:build-id",
+            value: build_id.clone(),
+        });
+    }
+    Component {
+        type_,
+        bom_ref: summ.filename.clone(),
+        name: soname.unwrap_or_else(|| fallback_name.to_string()),
+        hashes: vec![Hash {
+            alg: "SHA-256",
+            content: summ.sha256.clone(),
+        }],
+        properties,
+    }
+}
+
+impl Bom {
+    /// Add `root` and the resolved libraries in its closure
+    ///
+    /// A library shared by several inputs is only listed once; unresolved
+    /// libraries are left out, since there is no file to describe
+    pub fn add(&mut self, root: &ElfSummary, resolution: &Resolution) {
+        let mut listed: collections::BTreeSet<PathBuf> =
+            self.components.iter().map(|c| c.bom_ref.clone()).collect();
+        let root_name = root
+            .filename
+            .file_name()
+            .map_or(String::new(), |n| n.to_string_lossy().into_owned());
+        if listed.insert(root.filename.clone()) {
+            self.components
+                .push(component("application", root, &root_name));
+        }
+        for (name, dep) in &resolution.dependencies {
+            if let Some(dep) = dep {
+                if listed.insert(dep.filename.clone()) {
+                    self.components.push(component("library", dep, name));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies;
+    use crate::ld_cache::LdCache;
+    use crate::summarize;
+
+    fn resolve(summ: &ElfSummary) -> Resolution {
+        dependencies::resolve_dependencies(
+            &[PathBuf::from("/")],
+            &LdCache::default(),
+            summ,
+            false,
+            &[],
+            None,
+        )
+    }
+
+    fn dynamic_data(summ: &mut ElfSummary) -> &mut summarize::DynamicData {
+        match &mut summ.binary_type {
+            BinaryType::Static => panic!("Expected a dynamically-linked binary"),
+            BinaryType::Dynamic(dyn_data) => dyn_data,
+        }
+    }
+
+    #[test]
+    fn shared_libraries_are_listed_once() {
+        let path = PathBuf::from("tests/fixtures/dynamic");
+        let first = summarize::summarize_path(&path).unwrap();
+        // A second input in the same directory, so its `$ORIGIN` RPATH finds
+        // the same library
+        let mut second = summarize::summarize_path(&path).unwrap();
+        second.filename = PathBuf::from("tests/fixtures/dynamic2");
+
+        let mut bom = Bom::default();
+        bom.add(&first, &resolve(&first));
+        bom.add(&second, &resolve(&second));
+        let components: Vec<(&str, &str)> = bom
+            .components
+            .iter()
+            .map(|c| (c.type_, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            components,
+            vec![
+                ("application", "dynamic"),
+                ("library", "libfixture.so"),
+                ("application", "dynamic2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_libraries_are_skipped() {
+        let mut summ = summarize::summarize_path(&PathBuf::from("tests/fixtures/dynamic")).unwrap();
+        dynamic_data(&mut summ)
+            .deps
+            .push(String::from("libmissing.so.1"));
+        let resolution = resolve(&summ);
+        assert!(resolution.dependencies["libmissing.so.1"].is_none());
+
+        let mut bom = Bom::default();
+        bom.add(&summ, &resolution);
+        assert_eq!(bom.components.len(), 2);
+        assert!(bom.components.iter().all(|c| c.name != "libmissing.so.1"));
+    }
+
+    #[test]
+    fn libraries_are_named_by_soname() {
+        let summ = summarize::summarize_path(&PathBuf::from("tests/fixtures/dynamic")).unwrap();
+        let mut resolution = resolve(&summ);
+        let mut bom = Bom::default();
+        bom.add(&summ, &resolution);
+        assert_eq!(bom.components[1].name, "libfixture.so");
+
+        // The SONAME is used even if the library was needed by another name,
+        // and the name it was needed by is only used without a SONAME
+        let lib = resolution
+            .dependencies
+            .get_mut("libfixture.so")
+            .and_then(|dep| dep.as_mut())
+            .unwrap();
+        dynamic_data(lib).soname = Some(String::from("libfixture.so.1"));
+        let mut bom = Bom::default();
+        bom.add(&summ, &resolution);
+        assert_eq!(bom.components[1].name, "libfixture.so.1");
+
+        let lib = resolution
+            .dependencies
+            .get_mut("libfixture.so")
+            .and_then(|dep| dep.as_mut())
+            .unwrap();
+        dynamic_data(lib).soname = None;
+        let mut bom = Bom::default();
+        bom.add(&summ, &resolution);
+        assert_eq!(bom.components[1].name, "libfixture.so");
+        assert_eq!(bom.components[0].name, "dynamic");
+    }
+}
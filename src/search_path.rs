@@ -1,38 +1,270 @@
+use serde::Serialize;
 use std::env;
 use std::path::{Path, PathBuf};
 
-use crate::summarize::ElfSummary;
+use crate::ld_cache::LdCache;
+use crate::summarize::{BinaryType, ElfSummary};
 
-/// Compute the shared library search path based on system defaults and `LD_LIBRARY_PATH`
+/// Where a directory on the library search path came from
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchPathSource {
+    /// The `DT_RPATH` of the binary
+    Rpath,
+    /// The `LD_LIBRARY_PATH` environment variable
+    LdLibraryPath,
+    /// The `DT_RUNPATH` of the binary
+    Runpath,
+    /// The cache of library locations maintained by `ldconfig`
+    LdCache,
+    /// One of the default system library directories
+    Default,
+}
+
+impl std::fmt::Display for SearchPathSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SearchPathSource::Rpath => write!(f, "RPATH"),
+            SearchPathSource::LdLibraryPath => write!(f, "LD_LIBRARY_PATH"),
+            SearchPathSource::Runpath => write!(f, "RUNPATH"),
+            SearchPathSource::LdCache => write!(f, "ld.so.cache"),
+            SearchPathSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// A directory on the library search path, tagged with its source
+pub struct SearchDir {
+    pub dir: PathBuf,
+    pub source: SearchPathSource,
+}
+
+/// Expand a `DT_RPATH` or `DT_RUNPATH` entry into directories
+///
+/// `$ORIGIN` refers to the directory containing the binary; other absolute
+/// paths are interpreted relative to each of the sysroots (in order)
+fn expand_dynamic_path(sysroots: &[PathBuf], summ: &ElfSummary, dir: &str) -> Vec<PathBuf> {
+    let origin = summ
+        .filename
+        .parent()
+        .map_or(String::from("."), |p| p.to_string_lossy().into_owned());
+    if dir.starts_with("$ORIGIN") || dir.starts_with("${ORIGIN}") {
+        return vec![PathBuf::from(
+            dir.replace("${ORIGIN}", &origin)
+                .replace("$ORIGIN", &origin),
+        )];
+    }
+    match Path::new(dir).strip_prefix("/") {
+        Ok(relative) => sysroots.iter().map(|root| root.join(relative)).collect(),
+        Err(_) => vec![PathBuf::from(dir)],
+    }
+}
+
+fn push_dynamic_paths(
+    paths: &mut Vec<SearchDir>,
+    sysroots: &[PathBuf],
+    summ: &ElfSummary,
+    dirs: &[String],
+    source: SearchPathSource,
+) {
+    for dir in dirs.iter().filter(|dir| !dir.is_empty()) {
+        for expanded in expand_dynamic_path(sysroots, summ, dir) {
+            paths.push(SearchDir {
+                dir: expanded,
+                source,
+            });
+        }
+    }
+}
+
+fn dynamic_paths(summ: &ElfSummary) -> (&[String], &[String]) {
+    match &summ.binary_type {
+        BinaryType::Static => (&[], &[]),
+        BinaryType::Dynamic(dyn_data) => (dyn_data.rpath.as_slice(), dyn_data.runpath.as_slice()),
+    }
+}
+
+/// Compute the shared library search path used to find `lib_name` for the
+/// first module in `loaders` based on system defaults, `LD_LIBRARY_PATH`, the
+/// `DT_RPATH`/`DT_RUNPATH` of the modules, and the `ld_cache`
+///
+/// The `loaders` are the module that needs the library followed by the chain of
+/// modules that led to it being loaded, ending with the root binary (so a
+/// dependency of the root binary has just the root binary).  `$ORIGIN` in the
+/// entries of each module refers to its own directory.
 ///
-/// This does not yet consult the top-level summary to find DT_RUNPATH, but it needs to
-pub fn search_path(sysroot: &Path, _summ: &ElfSummary) -> Vec<PathBuf> {
+/// Each directory is searched under each of the `sysroots` in turn (e.g., the
+/// layers of a container image, from the top down) before moving on to the next
+/// directory
+///
+/// See Note [Search Path]
+pub fn search_path(
+    sysroots: &[PathBuf],
+    ld_cache: &LdCache,
+    loaders: &[&ElfSummary],
+    lib_name: &str,
+) -> Vec<SearchDir> {
     let mut paths = Vec::new();
+    let (_, runpath) = match loaders.first() {
+        Some(requester) => dynamic_paths(requester),
+        None => (&[][..], &[][..]),
+    };
+
+    // The loader ignores DT_RPATH if DT_RUNPATH is present; otherwise, the
+    // DT_RPATH of each module in the chain applies, as long as that module
+    // does not have a DT_RUNPATH itself
+    if runpath.is_empty() {
+        for loader in loaders {
+            let (loader_rpath, loader_runpath) = dynamic_paths(loader);
+            if loader_runpath.is_empty() {
+                push_dynamic_paths(
+                    &mut paths,
+                    sysroots,
+                    loader,
+                    loader_rpath,
+                    SearchPathSource::Rpath,
+                );
+            }
+        }
+    }
 
     match env::var("LD_LIBRARY_PATH") {
         Err(_) => {}
         Ok(path_str) => {
             for path in env::split_paths(&path_str) {
-                paths.push(path);
+                paths.push(SearchDir {
+                    dir: path,
+                    source: SearchPathSource::LdLibraryPath,
+                });
             }
         }
     }
 
+    if let Some(requester) = loaders.first() {
+        push_dynamic_paths(
+            &mut paths,
+            sysroots,
+            requester,
+            runpath,
+            SearchPathSource::Runpath,
+        );
+    }
+
+    // The cache lists the paths of libraries on the target system
+    for dir in ld_cache.directories(lib_name) {
+        let relative = dir.strip_prefix("/").unwrap_or(dir);
+        for sysroot in sysroots {
+            paths.push(SearchDir {
+                dir: sysroot.join(relative),
+                source: SearchPathSource::LdCache,
+            });
+        }
+    }
+
     // The default paths used by the dynamic loader; note that this could vary
     // somewhat by system, so this list may need to be expanded
-    paths.push(sysroot.join(PathBuf::from("lib")));
-    paths.push(sysroot.join(PathBuf::from("lib64")));
-    paths.push(sysroot.join(PathBuf::from("usr/lib")));
-    paths.push(sysroot.join(PathBuf::from("usr/lib64")));
+    for dir in ["lib", "lib64", "usr/lib", "usr/lib64"] {
+        for sysroot in sysroots {
+            paths.push(SearchDir {
+                dir: sysroot.join(PathBuf::from(dir)),
+                source: SearchPathSource::Default,
+            });
+        }
+    }
 
     paths
 }
 
 /* Note [Search Path]
 
-1. Paths specified via DT_RPATH (deprecated, applies to *all* binary modules)
+1. Paths specified via DT_RPATH (deprecated) of the module that needs the
+   library, followed by those of the module that loaded it, and so on up to
+   the executable (whose DT_RPATH therefore applies to *all* modules)
 2. Paths in LD_LIBRARY_PATH
 3. Paths in DT_RUNPATH (note: only applies to dependencies of the binary being looked up)
-4. Default paths
+4. The paths recorded for the library in ld.so.cache
+5. Default paths
+
+With multiple sysroots (e.g., the layers of an overlayfs-based image), each
+absolute directory is tried under every sysroot, in order, before the next
+directory; this matches the view of the merged filesystem, where upper layers
+shadow lower ones.
+
+DT_RPATH is ignored entirely if the binary also has a DT_RUNPATH; a module in
+the chain that has a DT_RUNPATH contributes nothing to the search for the
+dependencies of the modules it loads.
+
+Each library in the closure is searched for on the search path of the module
+that first needed it, with $ORIGIN expanded to the directory of that module
+(not of the executable); this matters for plugin-style libraries that find
+their own dependencies relative to themselves.  The chain is the one through
+which each library was first reached in the breadth-first search, which is
+the order the loader maps them in.
 
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summarize;
+
+    /// A summary of the fixture library at `filename`, with the given search paths
+    fn module(filename: &str, rpath: &[&str], runpath: &[&str]) -> ElfSummary {
+        let mut summ =
+            summarize::summarize_path(&PathBuf::from("tests/fixtures/libfixture.so")).unwrap();
+        summ.filename = PathBuf::from(filename);
+        if let BinaryType::Dynamic(dyn_data) = &mut summ.binary_type {
+            dyn_data.rpath = rpath.iter().map(|s| s.to_string()).collect();
+            dyn_data.runpath = runpath.iter().map(|s| s.to_string()).collect();
+        }
+        summ
+    }
+
+    /// The directories from `DT_RPATH` and `DT_RUNPATH` on the search path
+    /// (the rest depend on the environment and the system)
+    fn dynamic_dirs(loaders: &[&ElfSummary]) -> Vec<(PathBuf, SearchPathSource)> {
+        search_path(
+            &[PathBuf::from("/")],
+            &LdCache::default(),
+            loaders,
+            "libc.so",
+        )
+        .into_iter()
+        .filter(|d| d.source == SearchPathSource::Rpath || d.source == SearchPathSource::Runpath)
+        .map(|d| (d.dir, d.source))
+        .collect()
+    }
+
+    #[test]
+    fn rpath_of_the_executable_applies_to_the_whole_chain() {
+        let a = module("/app/bin/a", &["/opt/a", "", "$ORIGIN/../lib"], &[]);
+        let b = module("/app/lib/libb.so", &[], &[]);
+        let expected = vec![
+            (PathBuf::from("/opt/a"), SearchPathSource::Rpath),
+            (PathBuf::from("/app/bin/../lib"), SearchPathSource::Rpath),
+        ];
+        // B needed by A, and then C needed by B
+        assert_eq!(dynamic_dirs(&[&a]), expected);
+        assert_eq!(dynamic_dirs(&[&b, &a]), expected);
+    }
+
+    #[test]
+    fn runpath_disables_rpath() {
+        let a = module("/app/bin/a", &["/opt/a"], &[]);
+        let b = module("/app/lib/libb.so", &[], &["$ORIGIN/private"]);
+        let d = module("/app/lib/private/libd.so", &[], &[]);
+
+        // The RUNPATH of B only applies to its own dependencies, and then the
+        // RPATH of A does not apply at all
+        assert_eq!(
+            dynamic_dirs(&[&b, &a]),
+            vec![(PathBuf::from("/app/lib/private"), SearchPathSource::Runpath)]
+        );
+        // B contributes nothing to the search for the dependencies of D, but
+        // the RPATH of A still applies
+        assert_eq!(
+            dynamic_dirs(&[&d, &b, &a]),
+            vec![(PathBuf::from("/opt/a"), SearchPathSource::Rpath)]
+        );
+    }
+}
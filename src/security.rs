@@ -0,0 +1,391 @@
+use object::elf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::options::SecurityRequirement;
+use crate::search_path::SearchPathSource;
+use crate::summarize::{BinaryType, ElfSummary};
+
+// Feature bits from the `GNU_PROPERTY_*_FEATURE_1_AND` properties
+const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 1 << 0;
+const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 1 << 1;
+const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 1 << 0;
+
+/// The state of relocation read-only protection
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Relro {
+    /// No `PT_GNU_RELRO` segment
+    None,
+    /// A `PT_GNU_RELRO` segment, but lazy binding leaves the GOT writable
+    Partial,
+    /// A `PT_GNU_RELRO` segment with immediate binding
+    Full,
+}
+
+/// Whether or not a binary is position independent
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pie {
+    No,
+    Yes,
+    /// A shared library (which is always position independent)
+    SharedObject,
+}
+
+/// Control-flow Enforcement Technology (x86) support
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cet {
+    None,
+    /// Indirect branch tracking only
+    Ibt,
+    /// Shadow stacks only
+    Shstk,
+    /// Both indirect branch tracking and shadow stacks
+    Full,
+}
+
+/// Why a `DT_RPATH` or `DT_RUNPATH` entry could let another user choose the
+/// libraries that are loaded into the binary
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RpathIssue {
+    /// An empty entry, which the loader treats as the current directory
+    Empty,
+    /// The current directory (`.`)
+    CurrentDirectory,
+    /// A relative path, which is resolved against the current directory
+    /// rather than the binary (unlike `$ORIGIN`)
+    Relative,
+    /// A directory that any user can write to
+    WorldWritable,
+}
+
+impl std::fmt::Display for RpathIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RpathIssue::Empty => write!(f, "empty entry (the current directory)"),
+            RpathIssue::CurrentDirectory => write!(f, "the current directory"),
+            RpathIssue::Relative => write!(f, "relative path"),
+            RpathIssue::WorldWritable => write!(f, "world-writable directory"),
+        }
+    }
+}
+
+/// A search path entry of the binary that is insecure
+#[derive(Serialize)]
+pub struct InsecureRpath {
+    /// Whether the entry is from `DT_RPATH` or `DT_RUNPATH`
+    pub source: SearchPathSource,
+    /// The entry as written in the binary (i.e., unexpanded)
+    pub entry: String,
+    pub issue: RpathIssue,
+}
+
+impl std::fmt::Display for InsecureRpath {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} '{}': {}", self.source, self.entry, self.issue)
+    }
+}
+
+/// The hardening properties of a single binary
+#[derive(Serialize)]
+pub struct SecurityReport {
+    pub file: PathBuf,
+    pub nx: bool,
+    pub pie: Pie,
+    pub relro: Relro,
+    pub canary: bool,
+    pub fortify: bool,
+    pub cet: Cet,
+    pub bti: bool,
+    /// Whether there are relocations against read-only segments (text
+    /// relocations), which force the loader to make code pages writable
+    pub textrel: bool,
+    /// The `DT_RPATH`/`DT_RUNPATH` entries used by the loader that are insecure
+    pub insecure_rpaths: Vec<InsecureRpath>,
+}
+
+fn has_segment(summ: &ElfSummary, seg_type: u32) -> bool {
+    summ.segments.iter().any(|s| s.type_ == seg_type)
+}
+
+fn references_symbol<P: Fn(&str) -> bool>(summ: &ElfSummary, pred: P) -> bool {
+    match &summ.binary_type {
+        BinaryType::Static => false,
+        BinaryType::Dynamic(dyn_data) => dyn_data
+            .dynamic_symbol_refs
+            .iter()
+            .any(|r| pred(&r.symbol.name)),
+    }
+}
+
+/// The stack is non-executable if there is a `PT_GNU_STACK` without `PF_X`
+///
+/// Without a `PT_GNU_STACK` segment, the loader defaults to an executable stack.
+pub fn nx(summ: &ElfSummary) -> bool {
+    summ.segments
+        .iter()
+        .find(|s| s.type_ == elf::PT_GNU_STACK)
+        .is_some_and(|s| s.flags & elf::PF_X == 0)
+}
+
+pub fn pie(summ: &ElfSummary) -> Pie {
+    if summ.file_type != elf::ET_DYN {
+        return Pie::No;
+    }
+
+    let flagged_pie = match &summ.binary_type {
+        BinaryType::Static => false,
+        BinaryType::Dynamic(dyn_data) => dyn_data.has_flag_1(elf::DF_1_PIE),
+    };
+    if flagged_pie || has_segment(summ, elf::PT_INTERP) {
+        Pie::Yes
+    } else {
+        Pie::SharedObject
+    }
+}
+
+pub fn relro(summ: &ElfSummary) -> Relro {
+    if !has_segment(summ, elf::PT_GNU_RELRO) {
+        return Relro::None;
+    }
+
+    let bind_now = match &summ.binary_type {
+        BinaryType::Static => false,
+        BinaryType::Dynamic(dyn_data) => {
+            dyn_data.bind_now
+                || dyn_data.has_flag(elf::DF_BIND_NOW)
+                || dyn_data.has_flag_1(elf::DF_1_NOW)
+        }
+    };
+    if bind_now {
+        Relro::Full
+    } else {
+        Relro::Partial
+    }
+}
+
+/// Stack protectors call into libc when they detect corruption
+pub fn canary(summ: &ElfSummary) -> bool {
+    references_symbol(summ, |name| {
+        name == "__stack_chk_fail" || name == "__stack_chk_guard"
+    })
+}
+
+/// `_FORTIFY_SOURCE` replaces calls to string/memory functions with `__*_chk` variants
+pub fn fortify(summ: &ElfSummary) -> bool {
+    references_symbol(summ, |name| {
+        name.starts_with("__") && name.ends_with("_chk") && name != "__stack_chk_fail"
+    })
+}
+
+pub fn cet(summ: &ElfSummary) -> Cet {
+    if summ.machine != elf::EM_X86_64 && summ.machine != elf::EM_386 {
+        return Cet::None;
+    }
+
+    let features = summ.feature_1_and.unwrap_or(0);
+    let ibt = features & GNU_PROPERTY_X86_FEATURE_1_IBT != 0;
+    let shstk = features & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0;
+    match (ibt, shstk) {
+        (true, true) => Cet::Full,
+        (true, false) => Cet::Ibt,
+        (false, true) => Cet::Shstk,
+        (false, false) => Cet::None,
+    }
+}
+
+pub fn bti(summ: &ElfSummary) -> bool {
+    summ.machine == elf::EM_AARCH64
+        && summ.feature_1_and.unwrap_or(0) & GNU_PROPERTY_AARCH64_FEATURE_1_BTI != 0
+}
+
+/// Text relocations are flagged by `DT_TEXTREL` or the `DF_TEXTREL` bit in `DT_FLAGS`
+pub fn textrel(summ: &ElfSummary) -> bool {
+    match &summ.binary_type {
+        BinaryType::Static => false,
+        BinaryType::Dynamic(dyn_data) => dyn_data.textrel || dyn_data.has_flag(elf::DF_TEXTREL),
+    }
+}
+
+#[cfg(unix)]
+fn world_writable(dir: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(dir).is_ok_and(|m| m.permissions().mode() & 0o002 != 0)
+}
+
+#[cfg(not(unix))]
+fn world_writable(_dir: &Path) -> bool {
+    false
+}
+
+fn rpath_issue(summ: &ElfSummary, entry: &str) -> Option<RpathIssue> {
+    if entry.is_empty() {
+        return Some(RpathIssue::Empty);
+    }
+    if entry == "." || entry == "./" {
+        return Some(RpathIssue::CurrentDirectory);
+    }
+
+    let dir = if let Some(rest) = entry
+        .strip_prefix("$ORIGIN")
+        .or_else(|| entry.strip_prefix("${ORIGIN}"))
+    {
+        let origin = summ.filename.parent().unwrap_or_else(|| Path::new("."));
+        origin.join(rest.trim_start_matches('/'))
+    } else if entry.starts_with('$') {
+        // Other dynamic string tokens (e.g., `$LIB`) depend on the system
+        // running the binary, so there is nothing to check
+        return None;
+    } else if Path::new(entry).is_relative() {
+        return Some(RpathIssue::Relative);
+    } else {
+        PathBuf::from(entry)
+    };
+    if world_writable(&dir) {
+        Some(RpathIssue::WorldWritable)
+    } else {
+        None
+    }
+}
+
+/// Find the insecure entries on the search path of a binary
+///
+/// Only the entries that the loader uses are checked (i.e., `DT_RPATH` is
+/// ignored if there is a `DT_RUNPATH`).  Writability is checked against the
+/// directories on this system, with `$ORIGIN` expanded to the directory
+/// containing the binary.
+pub fn insecure_rpaths(summ: &ElfSummary) -> Vec<InsecureRpath> {
+    let dyn_data = match &summ.binary_type {
+        BinaryType::Static => return Vec::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data,
+    };
+    let (source, entries) = if dyn_data.runpath.is_empty() {
+        (SearchPathSource::Rpath, &dyn_data.rpath)
+    } else {
+        (SearchPathSource::Runpath, &dyn_data.runpath)
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            rpath_issue(summ, entry).map(|issue| InsecureRpath {
+                source,
+                entry: entry.clone(),
+                issue,
+            })
+        })
+        .collect()
+}
+
+/// Compute all of the hardening properties of a binary
+///
+/// Every output format is built from this report so that they agree
+pub fn security_report(summ: &ElfSummary) -> SecurityReport {
+    SecurityReport {
+        file: summ.filename.clone(),
+        nx: nx(summ),
+        pie: pie(summ),
+        relro: relro(summ),
+        canary: canary(summ),
+        fortify: fortify(summ),
+        cet: cet(summ),
+        bti: bti(summ),
+        textrel: textrel(summ),
+        insecure_rpaths: insecure_rpaths(summ),
+    }
+}
+
+/// Returns true if the binary described by the report satisfies the requirement
+pub fn meets_requirement(report: &SecurityReport, req: SecurityRequirement) -> bool {
+    match req {
+        SecurityRequirement::Nx => report.nx,
+        SecurityRequirement::Pie => report.pie != Pie::No,
+        SecurityRequirement::Relro => report.relro != Relro::None,
+        SecurityRequirement::RelroFull => report.relro == Relro::Full,
+        SecurityRequirement::Canary => report.canary,
+        SecurityRequirement::Fortify => report.fortify,
+        SecurityRequirement::Ibt => report.cet == Cet::Ibt || report.cet == Cet::Full,
+        SecurityRequirement::Shstk => report.cet == Cet::Shstk || report.cet == Cet::Full,
+        SecurityRequirement::Bti => report.bti,
+        SecurityRequirement::NoTextrel => !report.textrel,
+        SecurityRequirement::NoInsecureRpath => report.insecure_rpaths.is_empty(),
+    }
+}
+
+/// Compute the subset of requirements that the binary does not satisfy
+pub fn unmet_requirements(
+    report: &SecurityReport,
+    reqs: &[SecurityRequirement],
+) -> Vec<SecurityRequirement> {
+    reqs.iter()
+        .copied()
+        .filter(|req| !meets_requirement(report, *req))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summarize;
+
+    /// A summary of the fixture library, as if it were located at `filename`
+    fn summary_at(filename: &Path) -> ElfSummary {
+        let mut summ =
+            summarize::summarize_path(&PathBuf::from("tests/fixtures/libfixture.so")).unwrap();
+        summ.filename = filename.to_path_buf();
+        summ
+    }
+
+    #[test]
+    fn empty_and_current_directory_entries() {
+        let summ = summary_at(Path::new("/usr/lib/libfixture.so"));
+        assert_eq!(rpath_issue(&summ, ""), Some(RpathIssue::Empty));
+        assert_eq!(rpath_issue(&summ, "."), Some(RpathIssue::CurrentDirectory));
+        assert_eq!(rpath_issue(&summ, "./"), Some(RpathIssue::CurrentDirectory));
+    }
+
+    #[test]
+    fn relative_entries() {
+        let summ = summary_at(Path::new("/usr/lib/libfixture.so"));
+        assert_eq!(rpath_issue(&summ, "lib"), Some(RpathIssue::Relative));
+        assert_eq!(rpath_issue(&summ, "../lib"), Some(RpathIssue::Relative));
+        assert_eq!(rpath_issue(&summ, "$LIB"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn origin_is_relative_to_the_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("binary-walkr-rpath-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let private = dir.join("private");
+        let shared = dir.join("shared");
+        std::fs::create_dir_all(&private).unwrap();
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::set_permissions(&private, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        let summ = summary_at(&private.join("libfixture.so"));
+        assert_eq!(rpath_issue(&summ, "$ORIGIN"), None);
+        assert_eq!(
+            rpath_issue(&summ, "$ORIGIN/../shared"),
+            Some(RpathIssue::WorldWritable)
+        );
+        assert_eq!(
+            rpath_issue(&summ, "${ORIGIN}/../shared"),
+            Some(RpathIssue::WorldWritable)
+        );
+
+        let summ = summary_at(&shared.join("libfixture.so"));
+        assert_eq!(
+            rpath_issue(&summ, "$ORIGIN"),
+            Some(RpathIssue::WorldWritable)
+        );
+        assert_eq!(rpath_issue(&summ, "$ORIGIN/../private"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
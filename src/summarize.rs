@@ -1,17 +1,37 @@
 use object::elf;
 use object::read::elf as elf_reader;
-use object::read::elf::{Dyn, FileHeader, Sym, SectionHeader, ProgramHeader};
-use object::read::{SectionIndex, StringTable};
-use object::Endianness;
-use std::fs;
+use object::read::elf::{CompressionHeader, Dyn, FileHeader, Sym, SectionHeader, ProgramHeader, Rela};
+use object::read::{CompressedData, CompressionFormat, SectionIndex, StringTable};
+use object::{Endian, Endianness};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::archive;
+use crate::core_dump::{self, CoreInfo};
+use crate::hash_table;
+use crate::pe;
+use crate::timings;
 
 #[derive(thiserror::Error, Debug)]
 pub enum WalkError {
-    #[error("Missing expected `.dynstr` section")]
+    #[error("Missing expected dynamic string table (no `.dynstr` section or `DT_STRTAB`)")]
     MissingExpectedDynstrSection,
-    #[error("Missing expected `.dynsym` section")]
+    #[error("Missing expected dynamic symbol table (no `.dynsym` section or `DT_SYMTAB` and hash table)")]
     MissingExpectedDynsymSection,
+    #[error("{0:?} is not an ELF file")]
+    NotAnElfFile(PathBuf),
+    #[error("Invalid symbol version string at offset {0}")]
+    InvalidVersionString(u32),
+    #[error("Invalid compression header in section {0}")]
+    InvalidCompressionHeader(String),
+    #[error("Unsupported compression type {1} in section {0}")]
+    UnsupportedCompression(String, u32),
+    #[error("Implausible {0} count {1} for a file of {2} bytes")]
+    ImplausibleCount(String, u64, usize),
 }
 
 /// A (possibly) versioned symbol
@@ -22,27 +42,44 @@ pub struct VersionedSymbol {
 }
 
 impl VersionedSymbol {
-    fn new<Elf: FileHeader>(end: Elf::Endian, dyn_strings: &StringTable, sym: &Elf::Sym) -> Self {
+    fn new<Elf: FileHeader>(
+        end: Elf::Endian,
+        dyn_strings: &StringTable,
+        sym: &Elf::Sym,
+        version: Option<String>,
+    ) -> Self {
         let sym_name = sym
             .name(end, *dyn_strings)
             .map_or(String::from("<Error>"), |bytes| {
                 String::from_utf8_lossy(bytes).into_owned()
             });
-        // FIXME: Symbol versions are actually in another section - need to look them up
         VersionedSymbol {
             name: sym_name,
-            version: None,
+            version,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionedSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.version {
+            None => write!(f, "{}", self.name),
+            Some(v) => write!(f, "{}@{}", self.name, v),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SymbolType {
     Func,
     Object,
     Common,
     NoType,
     File,
+    /// Thread-local storage (`STT_TLS`)
+    Tls,
+    /// An indirect function, resolved by calling it at load time (`STT_GNU_IFUNC`)
+    Ifunc,
     Unknown,
 }
 
@@ -54,12 +91,14 @@ impl SymbolType {
             elf::STT_COMMON => SymbolType::Common,
             elf::STT_NOTYPE => SymbolType::NoType,
             elf::STT_FILE => SymbolType::File,
+            elf::STT_TLS => SymbolType::Tls,
+            elf::STT_GNU_IFUNC => SymbolType::Ifunc,
             _ => SymbolType::Unknown,
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SymbolBinding {
     Local,
     Global,
@@ -78,11 +117,43 @@ impl SymbolBinding {
     }
 }
 
+#[derive(Copy, Clone, Debug, Serialize)]
+pub enum SymbolVisibility {
+    Default,
+    Internal,
+    Hidden,
+    Protected,
+}
+
+impl SymbolVisibility {
+    fn new(v: u8) -> Self {
+        match v {
+            elf::STV_INTERNAL => SymbolVisibility::Internal,
+            elf::STV_HIDDEN => SymbolVisibility::Hidden,
+            elf::STV_PROTECTED => SymbolVisibility::Protected,
+            _ => SymbolVisibility::Default,
+        }
+    }
+}
+
 /// A reference to an external dynamic symbol in a binary
 pub struct DynamicSymbolReference {
     pub symbol: VersionedSymbol,
     pub type_: SymbolType,
     pub binding: SymbolBinding,
+    pub visibility: SymbolVisibility,
+}
+
+impl DynamicSymbolReference {
+    /// The letter that `nm` uses for an undefined reference: `U`, or `w`
+    /// (`v` for objects) if it is weak
+    pub fn nm_letter(&self) -> char {
+        match (self.binding, self.type_) {
+            (SymbolBinding::Weak, SymbolType::Object) => 'v',
+            (SymbolBinding::Weak, _) => 'w',
+            _ => 'U',
+        }
+    }
 }
 
 /// A dynamic symbol provided by this binary
@@ -90,8 +161,75 @@ pub struct ExportedDynamicSymbol {
     pub symbol: VersionedSymbol,
     pub type_: SymbolType,
     pub binding: SymbolBinding,
+    pub visibility: SymbolVisibility,
     pub size: u64,
     pub address: u64,
+    /// The section the symbol is defined in (`st_shndx`)
+    pub section: SymbolSection,
+}
+
+impl ExportedDynamicSymbol {
+    /// True if the virtual address `addr` is within the symbol (a symbol
+    /// without a size only contains its own address)
+    pub fn contains_address(&self, addr: u64) -> bool {
+        addr >= self.address && addr - self.address < std::cmp::max(self.size, 1)
+    }
+}
+
+/// The section that a symbol is defined in (from `st_shndx`)
+///
+/// The reserved indexes determine how the address of the symbol is interpreted
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SymbolSection {
+    /// `SHN_UNDEF`
+    Undefined,
+    /// `SHN_ABS`: the address is absolute, rather than relative to a section
+    Absolute,
+    /// `SHN_COMMON`: an unallocated common block (the address is its alignment)
+    Common,
+    /// An ordinary section (its index in the section header table)
+    Section(usize),
+    /// An ordinary section of the separate debug file (its index in the
+    /// section header table of the debug file, see `ElfSummary::debug_sections`)
+    DebugFile(usize),
+    /// Any other reserved index (e.g., a processor-specific one)
+    Reserved(u16),
+}
+
+impl SymbolSection {
+    fn new(shndx: u16) -> Self {
+        match shndx {
+            elf::SHN_UNDEF => SymbolSection::Undefined,
+            elf::SHN_ABS => SymbolSection::Absolute,
+            elf::SHN_COMMON => SymbolSection::Common,
+            idx if idx >= elf::SHN_LORESERVE => SymbolSection::Reserved(idx),
+            idx => SymbolSection::Section(usize::from(idx)),
+        }
+    }
+}
+
+/// The symbol versions that a binary requires from one of its dependencies (from `.gnu.version_r`)
+pub struct VersionRequirement {
+    /// The name of the library the versions are required from
+    pub library: String,
+    pub versions: Vec<String>,
+}
+
+/// A constructor or destructor run by the dynamic loader
+pub struct InitFunction {
+    pub address: u64,
+    /// The name of the symbol at `address`, if there is one
+    pub symbol: Option<String>,
+}
+
+/// The number of relocations processed by the dynamic loader, by source section
+#[derive(Copy, Clone, Debug, Default, Serialize)]
+pub struct RelocationCounts {
+    /// Relocations for PLT entries (`.rela.plt`, found through `DT_JMPREL`),
+    /// which are bound lazily unless the binary is linked with `BIND_NOW`
+    pub plt: usize,
+    /// All other relocations (`.rela.dyn`), which are always processed at load time
+    pub dynamic: usize,
 }
 
 /// Information summarizing the interface of a dynamically-linked binary or library
@@ -101,9 +239,226 @@ pub struct DynamicData {
     /// Dynamic symbols provided by this binary
     pub provided_dynamic_symbols: Vec<ExportedDynamicSymbol>,
     /// The names of libraries that this binary pulls in as dynamic dependencies
+    ///
+    /// These are in the order of the `DT_NEEDED` entries (which is the order the
+    /// loader searches them for symbols), with any repeated entries dropped
     pub deps: Vec<String>,
+    /// The name of the library (`DT_SONAME`), if it has one
+    pub soname: Option<String>,
+    /// The directories listed in `DT_RPATH` (unexpanded, e.g., `$ORIGIN` is left
+    /// as-is, and empty entries are kept)
+    pub rpath: Vec<String>,
+    /// The directories listed in `DT_RUNPATH` (unexpanded)
+    pub runpath: Vec<String>,
+    /// The libraries named by `DT_FILTER` entries
+    ///
+    /// The symbols that this library defines are resolved to the definitions
+    /// in these libraries instead
+    pub filters: Vec<String>,
+    /// The libraries named by `DT_AUXILIARY` entries, which provide the
+    /// definitions of the symbols of this library if they can be loaded
+    pub auxiliary_filters: Vec<String>,
+    /// The auditing libraries listed in `DT_AUDIT`
+    pub audit: Vec<String>,
+    /// The auditing libraries listed in `DT_DEPAUDIT` (which audit any
+    /// process that loads this library)
+    pub dependency_audit: Vec<String>,
+    /// The value of `DT_FLAGS` (zero if absent)
+    pub flags: u64,
+    /// The value of `DT_FLAGS_1` (zero if absent)
+    pub flags_1: u64,
+    /// True if the (legacy) `DT_BIND_NOW` tag is present
+    pub bind_now: bool,
+    /// True if the (legacy) `DT_TEXTREL` tag is present
+    pub textrel: bool,
+    /// The symbol versions defined by this binary (from `.gnu.version_d`)
+    pub version_definitions: Vec<String>,
+    /// The symbol versions required by this binary, grouped by library
+    pub version_requirements: Vec<VersionRequirement>,
+    /// The function named by `DT_INIT`, if any
+    pub init: Option<InitFunction>,
+    /// The function named by `DT_FINI`, if any
+    pub fini: Option<InitFunction>,
+    /// The contents of the `DT_INIT_ARRAY` (e.g., static constructors)
+    pub init_array: Vec<InitFunction>,
+    /// The contents of the `DT_FINI_ARRAY` (e.g., static destructors)
+    pub fini_array: Vec<InitFunction>,
+    /// The number of entries in the dynamic symbol table
+    pub dynamic_symbol_count: usize,
+    /// The number of dynamic symbols covered by each hash table (as found through the dynamic entries)
+    pub hash_symbol_counts: Vec<(HashTableKind, usize)>,
+    pub relocation_counts: RelocationCounts,
 }
 
+/// The names of the bits in `DT_FLAGS`
+const DYNAMIC_FLAGS: [(u32, &str); 5] = [
+    (elf::DF_ORIGIN, "DF_ORIGIN"),
+    (elf::DF_SYMBOLIC, "DF_SYMBOLIC"),
+    (elf::DF_TEXTREL, "DF_TEXTREL"),
+    (elf::DF_BIND_NOW, "DF_BIND_NOW"),
+    (elf::DF_STATIC_TLS, "DF_STATIC_TLS"),
+];
+
+/// The names of the bits in `DT_FLAGS_1`
+const DYNAMIC_FLAGS_1: [(u32, &str); 16] = [
+    (elf::DF_1_NOW, "DF_1_NOW"),
+    (elf::DF_1_GLOBAL, "DF_1_GLOBAL"),
+    (elf::DF_1_GROUP, "DF_1_GROUP"),
+    (elf::DF_1_NODELETE, "DF_1_NODELETE"),
+    (elf::DF_1_LOADFLTR, "DF_1_LOADFLTR"),
+    (elf::DF_1_INITFIRST, "DF_1_INITFIRST"),
+    (elf::DF_1_NOOPEN, "DF_1_NOOPEN"),
+    (elf::DF_1_ORIGIN, "DF_1_ORIGIN"),
+    (elf::DF_1_DIRECT, "DF_1_DIRECT"),
+    (elf::DF_1_INTERPOSE, "DF_1_INTERPOSE"),
+    (elf::DF_1_NODEFLIB, "DF_1_NODEFLIB"),
+    (elf::DF_1_NODUMP, "DF_1_NODUMP"),
+    (elf::DF_1_CONFALT, "DF_1_CONFALT"),
+    (elf::DF_1_ENDFILTEE, "DF_1_ENDFILTEE"),
+    (elf::DF_1_NODIRECT, "DF_1_NODIRECT"),
+    (elf::DF_1_PIE, "DF_1_PIE"),
+];
+
+/// Render the names of the bits set in `flags` (any unknown bits are rendered in hex)
+fn flag_names(flags: u64, names: &[(u32, &str)], res: &mut Vec<String>) {
+    let mut unknown = flags;
+    for (bit, name) in names {
+        if flags & u64::from(*bit) != 0 {
+            res.push(name.to_string());
+            unknown &= !u64::from(*bit);
+        }
+    }
+    if unknown != 0 {
+        res.push(format!("{:#x}", unknown));
+    }
+}
+
+impl DynamicData {
+    /// True if the given `DF_*` bit is set in `DT_FLAGS`
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & u64::from(flag) != 0
+    }
+
+    /// True if the given `DF_1_*` bit is set in `DT_FLAGS_1`
+    pub fn has_flag_1(&self, flag: u32) -> bool {
+        self.flags_1 & u64::from(flag) != 0
+    }
+
+    /// The filtees and auditors of the binary that are present, labeled by
+    /// their dynamic tags
+    ///
+    /// These change how symbols are resolved, so they are reported alongside
+    /// the dependencies (e.g., a filter library appears to define symbols that
+    /// are actually provided by its filtees)
+    pub fn filters_and_auditors(&self) -> Vec<(&'static str, &[String])> {
+        [
+            ("DT_FILTER", &self.filters),
+            ("DT_AUXILIARY", &self.auxiliary_filters),
+            ("DT_AUDIT", &self.audit),
+            ("DT_DEPAUDIT", &self.dependency_audit),
+        ]
+        .into_iter()
+        .filter(|(_, libs)| !libs.is_empty())
+        .map(|(tag, libs)| (tag, libs.as_slice()))
+        .collect()
+    }
+
+    /// The names of the bits set in `DT_FLAGS` and `DT_FLAGS_1` (e.g., `DF_BIND_NOW` and `DF_1_PIE`)
+    pub fn flag_names(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        flag_names(self.flags, &DYNAMIC_FLAGS, &mut res);
+        flag_names(self.flags_1, &DYNAMIC_FLAGS_1, &mut res);
+        res
+    }
+
+    /// The exported dynamic symbols of the given type
+    pub fn exported_symbols_of_type(
+        &self,
+        type_: SymbolType,
+    ) -> impl Iterator<Item = &ExportedDynamicSymbol> {
+        self.provided_dynamic_symbols
+            .iter()
+            .filter(move |sym| sym.type_ == type_)
+    }
+
+    /// The exported dynamic symbols that are functions (`STT_FUNC`)
+    pub fn exported_functions(&self) -> impl Iterator<Item = &ExportedDynamicSymbol> {
+        self.exported_symbols_of_type(SymbolType::Func)
+    }
+
+    /// The exported dynamic symbols that are data objects (`STT_OBJECT`)
+    pub fn exported_objects(&self) -> impl Iterator<Item = &ExportedDynamicSymbol> {
+        self.exported_symbols_of_type(SymbolType::Object)
+    }
+
+    /// All of the initialization and finalization functions, labeled by their source
+    ///
+    /// These are in the order that the loader runs them: `DT_INIT` runs before
+    /// the `DT_INIT_ARRAY`, while the `DT_FINI_ARRAY` runs before `DT_FINI`
+    pub fn init_fini_functions(&self) -> Vec<(&'static str, &InitFunction)> {
+        let mut funcs = Vec::new();
+        funcs.extend(self.init.iter().map(|f| ("DT_INIT", f)));
+        funcs.extend(self.init_array.iter().map(|f| ("DT_INIT_ARRAY", f)));
+        funcs.extend(self.fini_array.iter().map(|f| ("DT_FINI_ARRAY", f)));
+        funcs.extend(self.fini.iter().map(|f| ("DT_FINI", f)));
+        funcs
+    }
+
+    /// Describe any hash tables that disagree with the dynamic symbol table about the number of symbols
+    ///
+    /// A mismatch indicates that the binary has been tampered with or was
+    /// stripped incorrectly
+    pub fn hash_table_warnings(&self) -> Vec<String> {
+        self.hash_symbol_counts
+            .iter()
+            .filter(|(_, count)| *count != self.dynamic_symbol_count)
+            .map(|(kind, count)| {
+                format!(
+                    "The {} hash table covers {} dynamic symbols, but the dynamic symbol table has {}",
+                    kind, count, self.dynamic_symbol_count
+                )
+            })
+            .collect()
+    }
+
+    /// The minimum version of glibc that the binary can run against (e.g., `2.17`)
+    ///
+    /// This is the highest `GLIBC_x.y` version that the binary requires from
+    /// any library (glibc is split across `libc.so.6`, `libm.so.6`, the loader,
+    /// and others), or `None` if it does not require any glibc versions
+    pub fn minimum_glibc_version(&self) -> Option<String> {
+        self.version_requirements
+            .iter()
+            .flat_map(|req| req.versions.iter())
+            .filter_map(|v| v.strip_prefix("GLIBC_"))
+            .filter_map(|v| {
+                // Skips GLIBC_PRIVATE, which is not a release
+                let components: Result<Vec<u32>, _> = v.split('.').map(|c| c.parse()).collect();
+                components.ok().map(|c| (c, v))
+            })
+            .max()
+            .map(|(_, v)| v.to_string())
+    }
+
+    /// Sort the referenced and provided symbols by name (then version)
+    ///
+    /// The symbols are otherwise in the order of the dynamic symbol table, which
+    /// is deterministic but not meaningful.  The sort is stable, so multiple
+    /// definitions of the same versioned symbol keep their relative order.
+    pub fn sort_symbols(&mut self) {
+        self.dynamic_symbol_refs
+            .sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        self.provided_dynamic_symbols
+            .sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    }
+}
+
+fn by_address(a: &ExportedDynamicSymbol, b: &ExportedDynamicSymbol) -> std::cmp::Ordering {
+    (a.address, &a.symbol).cmp(&(b.address, &b.symbol))
+}
+
+// There is a single summary per binary, so the size of the static variant does not matter
+#[allow(clippy::large_enum_variant)]
 pub enum BinaryType {
     Static,
     Dynamic(DynamicData),
@@ -119,6 +474,23 @@ pub struct Section {
     pub flags: u64,
 }
 
+impl Section {
+    /// True if the contents of the section are compressed (`SHF_COMPRESSED`)
+    pub fn is_compressed(&self) -> bool {
+        self.flags & u64::from(elf::SHF_COMPRESSED) != 0
+    }
+
+    /// True if the section occupies the virtual address `addr` at run time
+    ///
+    /// The `.tbss` section is excluded, since it takes no space in the image
+    /// (its address overlaps the sections that follow it)
+    pub fn contains_address(&self, addr: u64) -> bool {
+        let allocated = self.flags & u64::from(elf::SHF_ALLOC) != 0;
+        let tbss = self.type_ == elf::SHT_NOBITS && self.flags & u64::from(elf::SHF_TLS) != 0;
+        allocated && !tbss && addr >= self.address && addr - self.address < self.size
+    }
+}
+
 pub struct Segment {
     pub type_: u32,
     pub flags: u32,
@@ -130,13 +502,773 @@ pub struct Segment {
     pub alignment: u64,
 }
 
+/// The memory mapped for the loadable (`PT_LOAD`) segments of a binary
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct LoadFootprint {
+    /// The number of loadable segments
+    pub segments: usize,
+    /// The total size of the segments in memory, which includes zero-filled
+    /// data like `.bss` (so it can be much larger than the file)
+    pub total: u64,
+    /// The size of the executable segments (code)
+    pub executable: u64,
+    /// The size of the writable (but not executable) segments (data)
+    pub writable: u64,
+    /// The size of the segments that are neither executable nor writable
+    /// (e.g., read-only data)
+    pub read_only: u64,
+}
+
 pub struct ElfSummary {
     pub endianness: Endianness,
     pub bit_size: usize,
     pub filename: PathBuf,
+    /// The ELF file type (`e_type`, e.g., `ET_EXEC` or `ET_DYN`)
+    pub file_type: u16,
+    /// The target machine (`e_machine`)
+    pub machine: u16,
+    /// The entry point address (`e_entry`, zero if there is none)
+    pub entry: u64,
     pub binary_type: BinaryType,
     pub sections: Vec<Section>,
     pub segments: Vec<Segment>,
+    /// The `GNU_PROPERTY_*_FEATURE_1_AND` bits from `.note.gnu.property`, if present
+    ///
+    /// The meaning of the bits depends on `machine` (e.g., IBT/SHSTK on x86 and
+    /// BTI/PAC on AArch64)
+    pub feature_1_and: Option<u32>,
+    /// The strings in the `.comment` section (usually the versions of the compilers used)
+    pub comment: Vec<String>,
+    /// The build ID (`NT_GNU_BUILD_ID`) in hex, if the binary has one
+    pub build_id: Option<String>,
+    /// The separate debug file named in `.gnu_debuglink`, if any
+    pub debug_link: Option<DebugLink>,
+    /// The separate debug file that `debug_symbols` were read from, if one was found
+    pub debug_file: Option<PathBuf>,
+    /// The symbols defined in the static symbol table of `debug_file` (for
+    /// stripped binaries, which have no `.symtab` of their own)
+    pub debug_symbols: Vec<ExportedDynamicSymbol>,
+    /// The sections of `debug_file`, which `debug_symbols` are defined in
+    pub debug_sections: Vec<Section>,
+    /// The SHA-256 of the contents of the file in hex (which identifies the
+    /// binary even if it has no build ID)
+    pub sha256: String,
+    /// The description of the crashed process, if this is a core dump (`ET_CORE`)
+    pub core: Option<CoreInfo>,
+    /// The modification time of the file when it was read (if available, e.g.,
+    /// not for members of archives)
+    pub modified: Option<SystemTime>,
+}
+
+/// The separate debug file named by the `.gnu_debuglink` section
+#[derive(Clone, Debug)]
+pub struct DebugLink {
+    /// The file name of the debug file (without any directory)
+    pub file: String,
+    /// The CRC-32 of the contents of the debug file
+    pub crc: u32,
+}
+
+/// A note (from an `SHT_NOTE` section or `PT_NOTE` segment) with its descriptor rendered as text
+#[derive(Serialize)]
+pub struct NoteDescriptor {
+    /// The section containing the note (or `PT_NOTE` if the section headers have been stripped)
+    pub section: String,
+    /// The name of the note's owner (e.g., `GNU`)
+    pub owner: String,
+    #[serde(rename = "type")]
+    pub type_: u32,
+    /// The decoded descriptor for the notes that are understood (e.g., the
+    /// build ID in hex); otherwise the descriptor as a string if it is
+    /// printable, or in hex if it is not
+    pub descriptor: String,
+}
+
+impl NoteDescriptor {
+    /// The symbolic name of the note type, for the owners with known note types
+    pub fn type_name(&self) -> Option<&'static str> {
+        if self.owner != "GNU" {
+            return None;
+        }
+        match self.type_ {
+            elf::NT_GNU_ABI_TAG => Some("NT_GNU_ABI_TAG"),
+            elf::NT_GNU_HWCAP => Some("NT_GNU_HWCAP"),
+            elf::NT_GNU_BUILD_ID => Some("NT_GNU_BUILD_ID"),
+            elf::NT_GNU_GOLD_VERSION => Some("NT_GNU_GOLD_VERSION"),
+            NT_GNU_PROPERTY_TYPE_0 => Some("NT_GNU_PROPERTY_TYPE_0"),
+            _ => None,
+        }
+    }
+}
+
+/// The human-meaningful strings of a binary that are relevant to dependency analysis
+#[derive(Serialize)]
+pub struct BinaryStrings {
+    pub file: PathBuf,
+    /// Every string in the dynamic string table (sonames, search paths, symbol names, and versions)
+    pub dynstr: Vec<String>,
+    /// The strings in the `.comment` section
+    pub comment: Vec<String>,
+    pub notes: Vec<NoteDescriptor>,
+}
+
+/// The kinds of symbol hash table that the dynamic loader can use for lookups
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HashTableKind {
+    /// `.gnu.hash` (`SHT_GNU_HASH`), which is faster but unsupported by very old loaders
+    Gnu,
+    /// `.hash` (`SHT_HASH`), the original System V hash table
+    SysV,
+}
+
+impl std::fmt::Display for HashTableKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HashTableKind::Gnu => write!(f, "GNU"),
+            HashTableKind::SysV => write!(f, "SysV"),
+        }
+    }
+}
+
+impl ElfSummary {
+    /// The symbol hash tables present in the binary
+    pub fn hash_tables(&self) -> Vec<HashTableKind> {
+        let mut tables = Vec::new();
+        if self.sections.iter().any(|s| s.type_ == elf::SHT_GNU_HASH) {
+            tables.push(HashTableKind::Gnu);
+        }
+        if self.sections.iter().any(|s| s.type_ == elf::SHT_HASH) {
+            tables.push(HashTableKind::SysV);
+        }
+        tables
+    }
+
+    /// A short name for the target architecture (e.g., `x86_64` or `aarch64`)
+    pub fn machine_name(&self) -> String {
+        let name = match self.machine {
+            elf::EM_386 => "x86",
+            elf::EM_X86_64 => "x86_64",
+            elf::EM_ARM => "arm",
+            elf::EM_AARCH64 => "aarch64",
+            elf::EM_MIPS => "mips",
+            elf::EM_PPC => "ppc",
+            elf::EM_PPC64 => "ppc64",
+            elf::EM_RISCV => "riscv",
+            elf::EM_S390 => "s390",
+            elf::EM_SPARC => "sparc",
+            elf::EM_SPARCV9 => "sparcv9",
+            other => return format!("machine-{}", other),
+        };
+        name.to_string()
+    }
+
+    /// Format an address zero-padded to the pointer width of the binary
+    ///
+    /// For example, a 32 bit binary renders `0x08048000` rather than a 16 digit value
+    pub fn format_address(&self, addr: u64) -> String {
+        format!("{:#0width$x}", addr, width = self.bit_size / 4 + 2)
+    }
+
+    /// Describe any loadable segments that the loader would reject or that
+    /// conflict with each other
+    ///
+    /// The file offset and virtual address of each `PT_LOAD` segment must be
+    /// congruent modulo its alignment (so that it can be mapped with `mmap`),
+    /// and loadable segments must not overlap in memory.  The entry point of
+    /// an executable must also be in an executable (`PF_X`) loadable segment,
+    /// or it will not run.  Violations indicate a corrupt or hand-patched binary.
+    pub fn segment_warnings(&self) -> Vec<String> {
+        let mut loads: Vec<&Segment> = self
+            .segments
+            .iter()
+            .filter(|s| s.type_ == elf::PT_LOAD)
+            .collect();
+        let mut warnings = Vec::new();
+        // The alignment of each segment is the page size the binary was linked
+        // for (e.g., 64K on some aarch64 systems), so the checks use it rather
+        // than assuming the page size of any particular system
+        for seg in &loads {
+            if seg.alignment > 1 && !seg.alignment.is_power_of_two() {
+                warnings.push(format!(
+                    "The PT_LOAD segment at {} has an alignment of {:#x}, which is not a power of two",
+                    self.format_address(seg.vaddr),
+                    seg.alignment
+                ));
+            } else if seg.alignment > 1 && seg.offset % seg.alignment != seg.vaddr % seg.alignment {
+                warnings.push(format!(
+                    "The PT_LOAD segment at {} has offset {:#x}, which is not congruent to its address modulo its alignment ({:#x})",
+                    self.format_address(seg.vaddr),
+                    seg.offset,
+                    seg.alignment
+                ));
+            }
+        }
+
+        loads.sort_by_key(|s| s.vaddr);
+        for pair in loads.windows(2) {
+            if pair[0].vaddr.saturating_add(pair[0].mem_size) > pair[1].vaddr {
+                warnings.push(format!(
+                    "The PT_LOAD segments at {} and {} overlap",
+                    self.format_address(pair[0].vaddr),
+                    self.format_address(pair[1].vaddr)
+                ));
+            }
+        }
+
+        // Shared libraries usually have no entry point, but position independent
+        // executables (which are also ET_DYN) do
+        let executable =
+            self.file_type == elf::ET_EXEC || (self.file_type == elf::ET_DYN && self.entry != 0);
+        if executable && !loads.is_empty() {
+            let entry_mapped = loads.iter().any(|s| {
+                s.flags & elf::PF_X != 0
+                    && self.entry >= s.vaddr
+                    && self.entry - s.vaddr < s.mem_size
+            });
+            if self.entry == 0 {
+                warnings.push(String::from("The executable has no entry point"));
+            } else if !entry_mapped {
+                warnings.push(format!(
+                    "The entry point {} is not in an executable PT_LOAD segment",
+                    self.format_address(self.entry)
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// The section containing the virtual address `addr` (e.g., `.text` for a
+    /// function), if any
+    pub fn section_containing(&self, addr: u64) -> Option<&Section> {
+        self.sections.iter().find(|s| s.contains_address(addr))
+    }
+
+    /// Sum the memory sizes of the loadable segments, split by their permissions
+    pub fn load_footprint(&self) -> LoadFootprint {
+        let mut footprint = LoadFootprint::default();
+        for seg in self.segments.iter().filter(|s| s.type_ == elf::PT_LOAD) {
+            footprint.segments += 1;
+            footprint.total += seg.mem_size;
+            if seg.flags & elf::PF_X != 0 {
+                footprint.executable += seg.mem_size;
+            } else if seg.flags & elf::PF_W != 0 {
+                footprint.writable += seg.mem_size;
+            } else {
+                footprint.read_only += seg.mem_size;
+            }
+        }
+        footprint
+    }
+
+    /// The section header that a symbol is defined in, if it is an ordinary section
+    fn symbol_section(&self, index: SymbolSection) -> Option<&Section> {
+        match index {
+            SymbolSection::Section(idx) => self.sections.get(idx),
+            SymbolSection::DebugFile(idx) => self.debug_sections.get(idx),
+            _ => None,
+        }
+    }
+
+    /// The name of the section that a symbol is defined in
+    ///
+    /// The reserved indexes are rendered symbolically (e.g., `ABS` for absolute symbols)
+    pub fn section_name(&self, index: SymbolSection) -> String {
+        match index {
+            SymbolSection::Undefined => String::from("UND"),
+            SymbolSection::Absolute => String::from("ABS"),
+            SymbolSection::Common => String::from("COMMON"),
+            SymbolSection::Section(idx) | SymbolSection::DebugFile(idx) => self
+                .symbol_section(index)
+                .map_or_else(|| format!("[{}]", idx), |s| s.name.clone()),
+            SymbolSection::Reserved(idx) => format!("{:#x}", idx),
+        }
+    }
+
+    /// The letter that `nm` uses for the type of a defined symbol
+    ///
+    /// Weak symbols are `W` (or `V` for objects), indirect functions are `i`,
+    /// and the rest depend on the section they are defined in (e.g., `T` for
+    /// code, `D` for data, `B` for uninitialized data, and `R` for read-only
+    /// data).  Local symbols use the lowercase letter.
+    pub fn nm_letter(&self, sym: &ExportedDynamicSymbol) -> char {
+        let section = self.symbol_section(sym.section);
+        let letter = match (sym.binding, sym.type_, sym.section) {
+            (SymbolBinding::Weak, SymbolType::Object | SymbolType::Tls, _) => 'V',
+            (SymbolBinding::Weak, _, _) => 'W',
+            (_, SymbolType::Ifunc, _) => 'i',
+            (_, _, SymbolSection::Absolute) => 'A',
+            (_, _, SymbolSection::Common) => 'C',
+            (_, _, SymbolSection::Section(_) | SymbolSection::DebugFile(_)) => match section {
+                Some(s) if s.flags & u64::from(elf::SHF_EXECINSTR) != 0 => 'T',
+                Some(s) if s.type_ == elf::SHT_NOBITS => 'B',
+                Some(s) if s.flags & u64::from(elf::SHF_WRITE) == 0 => 'R',
+                Some(_) => 'D',
+                None if sym.type_ == SymbolType::Func => 'T',
+                None => 'D',
+            },
+            (_, _, SymbolSection::Undefined | SymbolSection::Reserved(_)) => '?',
+        };
+        if sym.binding == SymbolBinding::Local {
+            letter.to_ascii_lowercase()
+        } else {
+            letter
+        }
+    }
+
+    /// Sort the dynamic symbols of the binary by name (see [`DynamicData::sort_symbols`])
+    pub fn sort_symbols(&mut self) {
+        if let BinaryType::Dynamic(dyn_data) = &mut self.binary_type {
+            dyn_data.sort_symbols();
+        }
+        self.debug_symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    }
+
+    /// Sort the defined symbols of the binary by address (and then by name)
+    ///
+    /// Undefined references have no address, so they keep their order and are
+    /// listed separately rather than all sorting before the defined symbols
+    pub fn sort_symbols_by_address(&mut self) {
+        if let BinaryType::Dynamic(dyn_data) = &mut self.binary_type {
+            dyn_data.provided_dynamic_symbols.sort_by(by_address);
+        }
+        self.debug_symbols.sort_by(by_address);
+    }
+
+    /// True if the binary has no static symbol table (`.symtab`)
+    pub fn is_stripped(&self) -> bool {
+        !self.sections.iter().any(|s| s.type_ == elf::SHT_SYMTAB)
+    }
+}
+
+/// The symbol version tables (`.gnu.version`, `.gnu.version_d`, and `.gnu.version_r`)
+///
+/// Each dynamic symbol has a corresponding entry in `.gnu.version` (the
+/// "versym") that indexes into the version definitions and requirements.
+struct SymbolVersions<'data> {
+    versym: &'data [elf::Versym<Endianness>],
+    /// The names of each version index
+    names: collections::BTreeMap<u16, String>,
+    definitions: Vec<String>,
+    requirements: Vec<VersionRequirement>,
+}
+
+impl<'data> SymbolVersions<'data> {
+    fn parse<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+        end: Elf::Endian,
+        bytes: &'data [u8],
+        sec_table: &elf_reader::SectionTable<'data, Elf>,
+        dyn_strings: StringTable<'data>,
+    ) -> anyhow::Result<Self> {
+        let mut names = collections::BTreeMap::new();
+        let mut definitions = Vec::new();
+        let mut requirements = Vec::new();
+        let lookup_string = |offset: u32| {
+            dyn_strings
+                .get(offset)
+                .map(|s| String::from_utf8_lossy(s).into_owned())
+                .map_err(|_| WalkError::InvalidVersionString(offset))
+        };
+
+        let versym = sec_table
+            .gnu_versym(end, bytes)?
+            .map_or(&[][..], |(versym, _)| versym);
+
+        if let Some((mut verdefs, _)) = sec_table.gnu_verdef(end, bytes)? {
+            while let Some((verdef, mut verdauxs)) = verdefs.next()? {
+                // The base version is the name of the file itself rather than a
+                // version that symbols can refer to
+                if verdef.vd_flags.get(end) & elf::VER_FLG_BASE != 0 {
+                    continue;
+                }
+                if let Some(verdaux) = verdauxs.next()? {
+                    let name = lookup_string(verdaux.vda_name.get(end))?;
+                    names.insert(verdef.vd_ndx.get(end), name.clone());
+                    definitions.push(name);
+                }
+            }
+        }
+
+        if let Some((mut verneeds, _)) = sec_table.gnu_verneed(end, bytes)? {
+            while let Some((verneed, mut vernauxs)) = verneeds.next()? {
+                let mut req = VersionRequirement {
+                    library: lookup_string(verneed.vn_file.get(end))?,
+                    versions: Vec::new(),
+                };
+                while let Some(vernaux) = vernauxs.next()? {
+                    let name = lookup_string(vernaux.vna_name.get(end))?;
+                    names.insert(vernaux.vna_other.get(end), name.clone());
+                    req.versions.push(name);
+                }
+                requirements.push(req);
+            }
+        }
+
+        Ok(SymbolVersions {
+            versym,
+            names,
+            definitions,
+            requirements,
+        })
+    }
+
+    /// The version of the dynamic symbol at the given index, if any
+    ///
+    /// The reserved indexes for local and global (unversioned) symbols have no name
+    fn version(&self, end: Endianness, sym_idx: usize) -> Option<String> {
+        let versym = self.versym.get(sym_idx)?;
+        let ver_idx = versym.0.get(end) & elf::VERSYM_VERSION;
+        self.names.get(&ver_idx).cloned()
+    }
+}
+
+/// The dynamic entries describing initialization and finalization functions
+///
+/// The arrays are recorded as (address, size in bytes) pairs
+#[derive(Default)]
+struct InitFiniEntries {
+    init: Option<u64>,
+    fini: Option<u64>,
+    init_array: (u64, u64),
+    fini_array: (u64, u64),
+}
+
+/// Map a virtual address to the file offset it is loaded from (if any)
+fn address_file_offset(segments: &[Segment], addr: u64) -> Option<u64> {
+    segments
+        .iter()
+        .find(|s| s.type_ == elf::PT_LOAD && addr >= s.vaddr && addr - s.vaddr < s.file_size)
+        .and_then(|s| s.offset.checked_add(addr - s.vaddr))
+}
+
+/// The addends of all `SHT_RELA` relocations, keyed by the address they apply to
+///
+/// Position independent binaries usually leave the function pointer arrays
+/// zeroed on disk and fill them in with relative relocations at load time.  A
+/// malformed relocation section is skipped (with a warning), in which case the
+/// values on disk are used for the addresses it covers.
+fn relocation_addends<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> collections::BTreeMap<u64, u64> {
+    let mut addends = collections::BTreeMap::new();
+    for (idx, hdr) in sec_table.iter().enumerate() {
+        let relas = match hdr.rela(end, bytes) {
+            Ok(Some((relas, _))) => relas,
+            Ok(None) => continue,
+            Err(err) => {
+                log::warn!("Skipping malformed relocation section [{}]: {}", idx, err);
+                continue;
+            }
+        };
+        for rela in relas {
+            let addend: i64 = rela.r_addend(end).into();
+            addends.insert(rela.r_offset(end).into(), addend as u64);
+        }
+    }
+    addends
+}
+
+/// The number of relocations in a section, or `None` if it is not a relocation section
+fn section_relocation_count<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    hdr: &Elf::SectionHeader,
+) -> object::read::Result<Option<usize>> {
+    if let Some((relas, _)) = hdr.rela(end, bytes)? {
+        return Ok(Some(relas.len()));
+    }
+    Ok(hdr.rel(end, bytes)?.map(|(rels, _)| rels.len()))
+}
+
+/// Count the relocations in the allocated relocation sections, splitting off the
+/// PLT relocations (the section at the `DT_JMPREL` address) from the others
+///
+/// The counts are informational, so a malformed relocation section is skipped
+/// (with a warning) rather than failing the whole summary
+fn relocation_counts<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    sec_table: &elf_reader::SectionTable<Elf>,
+    jmprel: Option<u64>,
+) -> RelocationCounts {
+    let mut counts = RelocationCounts::default();
+    for (idx, hdr) in sec_table.iter().enumerate() {
+        let flags: u64 = hdr.sh_flags(end).into();
+        if flags & u64::from(elf::SHF_ALLOC) == 0 {
+            continue;
+        }
+        let count = match section_relocation_count::<Elf>(end, bytes, hdr) {
+            Ok(Some(count)) => count,
+            Ok(None) => continue,
+            Err(err) => {
+                log::warn!("Skipping malformed relocation section [{}]: {}", idx, err);
+                continue;
+            }
+        };
+        if jmprel == Some(hdr.sh_addr(end).into()) {
+            counts.plt += count;
+        } else {
+            counts.dynamic += count;
+        }
+    }
+    counts
+}
+
+/// Reject a count of entries (each `entry_size` bytes) read from the binary if
+/// they could not possibly fit in the file
+///
+/// Crafted binaries can claim enormous counts, which would otherwise lead to
+/// huge allocations or very long loops
+fn check_plausible_count(
+    what: &str,
+    count: u64,
+    entry_size: usize,
+    bytes: &[u8],
+) -> Result<(), WalkError> {
+    if count > (bytes.len() / entry_size) as u64 {
+        return Err(WalkError::ImplausibleCount(
+            what.to_string(),
+            count,
+            bytes.len(),
+        ));
+    }
+    Ok(())
+}
+
+/// Read an array of function pointers of `size` bytes starting at the virtual
+/// address `addr` (from the dynamic entry named `what`)
+fn read_address_array<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    segments: &[Segment],
+    addends: &collections::BTreeMap<u64, u64>,
+    what: &str,
+    (addr, size): (u64, u64),
+) -> anyhow::Result<Vec<u64>> {
+    let word_size = if obj.is_class_32() { 4 } else { 8 };
+    check_plausible_count(what, size / word_size as u64, word_size, bytes)?;
+    let mut addrs = Vec::new();
+    for slot in (addr..addr.saturating_add(size)).step_by(word_size) {
+        let stored = match address_file_offset(segments, slot) {
+            None => 0,
+            Some(offset) => {
+                let offset = offset as usize;
+                match bytes.get(offset..offset.saturating_add(word_size)) {
+                    None => 0,
+                    Some(word) if word_size == 4 => end.read_u32_bytes(word.try_into()?).into(),
+                    Some(word) => end.read_u64_bytes(word.try_into()?),
+                }
+            }
+        };
+        let value = if stored == 0 {
+            addends.get(&slot).copied().unwrap_or(0)
+        } else {
+            stored
+        };
+        addrs.push(value);
+    }
+    Ok(addrs)
+}
+
+/// Read the initialization and finalization functions, naming them from the symbol tables
+///
+/// Names from the dynamic symbol table are preferred, but most constructors are
+/// local and only appear in `.symtab` (if the binary is not stripped)
+#[allow(clippy::type_complexity)]
+fn parse_init_functions<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<Elf>,
+    segments: &[Segment],
+    entries: &InitFiniEntries,
+    def_symbols: &[ExportedDynamicSymbol],
+) -> anyhow::Result<(
+    Option<InitFunction>,
+    Option<InitFunction>,
+    Vec<InitFunction>,
+    Vec<InitFunction>,
+)> {
+    let mut names = collections::BTreeMap::new();
+    for sym_def in def_symbols {
+        names
+            .entry(sym_def.address)
+            .or_insert_with(|| sym_def.symbol.to_string());
+    }
+    let symtab = sec_table.symbols(end, bytes, elf::SHT_SYMTAB)?;
+    for sym in symtab.symbols() {
+        if sym.st_type() != elf::STT_FUNC || sym.is_undefined(end) {
+            continue;
+        }
+        if let Ok(name) = sym.name(end, symtab.strings()) {
+            names
+                .entry(sym.st_value(end).into())
+                .or_insert_with(|| String::from_utf8_lossy(name).into_owned());
+        }
+    }
+
+    let to_function = |address: u64| InitFunction {
+        address,
+        symbol: names.get(&address).cloned(),
+    };
+
+    let addends = relocation_addends(end, bytes, sec_table);
+    let init_array = read_address_array(
+        end,
+        bytes,
+        obj,
+        segments,
+        &addends,
+        "DT_INIT_ARRAY entry",
+        entries.init_array,
+    )?;
+    let fini_array = read_address_array(
+        end,
+        bytes,
+        obj,
+        segments,
+        &addends,
+        "DT_FINI_ARRAY entry",
+        entries.fini_array,
+    )?;
+
+    Ok((
+        entries.init.map(to_function),
+        entries.fini.map(to_function),
+        init_array.into_iter().map(to_function).collect(),
+        fini_array.into_iter().map(to_function).collect(),
+    ))
+}
+
+/// Split a colon-separated `DT_RPATH` or `DT_RUNPATH` value into its directories
+///
+/// Empty entries are kept so that they can be reported as insecure (see
+/// `security::rpath_issue`); they are skipped when computing the search path
+fn split_search_path(path_bytes: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(path_bytes)
+        .split(':')
+        .map(String::from)
+        .collect()
+}
+
+/// Split a colon-separated list of libraries (e.g., `DT_AUDIT`) into its entries
+fn split_library_list(list_bytes: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(list_bytes)
+        .split(':')
+        .filter(|lib| !lib.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// The value of the first dynamic entry with the given tag, if any
+fn dynamic_value<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    dyn_entries: &[Elf::Dyn],
+    tag: u32,
+) -> Option<u64> {
+    dyn_entries
+        .iter()
+        .find(|d| d.tag32(end) == Some(tag))
+        .map(|d| d.d_val(end).into())
+}
+
+/// Find the dynamic entries, either through the `SHT_DYNAMIC` section or the
+/// `PT_DYNAMIC` segment (if the section headers have been stripped)
+fn dynamic_entries<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<'data, Elf>,
+) -> anyhow::Result<Option<&'data [Elf::Dyn]>> {
+    if let Some((dyn_entries, _dyn_idx)) = sec_table.dynamic(end, bytes)? {
+        return Ok(Some(dyn_entries));
+    }
+    for phdr in obj.program_headers(end, bytes)? {
+        if let Some(dyn_entries) = phdr.dynamic(end, bytes)? {
+            return Ok(Some(dyn_entries));
+        }
+    }
+    Ok(None)
+}
+
+/// The contents of the file starting at the address in the given dynamic entry
+fn dynamic_table_data<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    segments: &[Segment],
+    dyn_entries: &[Elf::Dyn],
+    tag: u32,
+) -> Option<&'data [u8]> {
+    let addr = dynamic_value::<Elf>(end, dyn_entries, tag)?;
+    let offset = address_file_offset(segments, addr)?;
+    bytes.get(offset as usize..)
+}
+
+/// The number of dynamic symbols covered by each of the hash tables (`DT_GNU_HASH` and `DT_HASH`)
+fn hash_symbol_counts<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    segments: &[Segment],
+    dyn_entries: &[Elf::Dyn],
+) -> Vec<(HashTableKind, usize)> {
+    let word_size = if obj.is_class_32() { 4 } else { 8 };
+    let mut counts = Vec::new();
+    if let Some(data) =
+        dynamic_table_data::<Elf>(end, bytes, segments, dyn_entries, elf::DT_GNU_HASH)
+    {
+        if let Some(count) = hash_table::gnu_symbol_count(end, data, word_size) {
+            counts.push((HashTableKind::Gnu, count));
+        }
+    }
+    if let Some(data) = dynamic_table_data::<Elf>(end, bytes, segments, dyn_entries, elf::DT_HASH) {
+        if let Some(count) = hash_table::sysv_symbol_count(end, data) {
+            counts.push((HashTableKind::SysV, count));
+        }
+    }
+    counts
+}
+
+/// Locate the dynamic string table through `DT_STRTAB` and `DT_STRSZ`
+fn dynamic_string_table<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    segments: &[Segment],
+    dyn_entries: &[Elf::Dyn],
+) -> Option<StringTable<'data>> {
+    let addr = dynamic_value::<Elf>(end, dyn_entries, elf::DT_STRTAB)?;
+    let size = dynamic_value::<Elf>(end, dyn_entries, elf::DT_STRSZ)?;
+    let offset = address_file_offset(segments, addr)?;
+    Some(StringTable::new(bytes, offset, offset.checked_add(size)?))
+}
+
+/// Locate the dynamic symbol table through `DT_SYMTAB`
+///
+/// The dynamic entries do not record the number of symbols, so it is recovered
+/// from the hash table (preferring `DT_GNU_HASH` over `DT_HASH`), as the
+/// dynamic loader would
+fn dynamic_symbol_table<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    segments: &[Segment],
+    dyn_entries: &[Elf::Dyn],
+    hash_counts: &[(HashTableKind, usize)],
+) -> Result<Option<&'data [Elf::Sym]>, WalkError> {
+    let count = match hash_counts.first() {
+        None => return Ok(None),
+        Some((_, count)) => *count,
+    };
+    let sym_size = std::mem::size_of::<Elf::Sym>();
+    check_plausible_count("dynamic symbol", count as u64, sym_size, bytes)?;
+    let data = match dynamic_table_data::<Elf>(end, bytes, segments, dyn_entries, elf::DT_SYMTAB) {
+        None => return Ok(None),
+        Some(data) => data,
+    };
+    Ok(object::pod::slice_from_bytes::<Elf::Sym>(data, count)
+        .ok()
+        .map(|(syms, _)| syms))
 }
 
 fn analyze_dependencies<Elf>(
@@ -148,40 +1280,136 @@ where
     Elf: elf_reader::FileHeader<Endian = Endianness>,
 {
     let end = obj.endian()?;
-    match sec_table.dynamic(end, bytes)? {
+    let segments: Vec<Segment> = obj
+        .program_headers(end, bytes)?
+        .iter()
+        .map(|s| parse_segment::<Elf>(end, s))
+        .collect();
+    match dynamic_entries(end, bytes, obj, sec_table)? {
         None => Ok(BinaryType::Static),
-        Some((dyn_entries, _dyn_idx)) => {
+        Some(dyn_entries) => {
             // We need strings from the dynamic string table (.strtab is for
             // *static* strings that hold symbol strings, which are not relevant
             // for resolving dynamic strings).
-            let (string_sec_idx, _string_sec) = sec_table
-                .section_by_name(end, ".dynstr".as_bytes())
-                .ok_or(WalkError::MissingExpectedDynstrSection)?;
-            let dyn_strings = sec_table.strings(end, bytes, SectionIndex(string_sec_idx))?;
+            //
+            // If the section headers have been stripped, the dynamic entries
+            // still point to the tables that the loader uses
+            let dyn_strings = match sec_table.section_by_name(end, ".dynstr".as_bytes()) {
+                Some((string_sec_idx, _string_sec)) => {
+                    sec_table.strings(end, bytes, SectionIndex(string_sec_idx))?
+                }
+                None => dynamic_string_table::<Elf>(end, bytes, &segments, dyn_entries)
+                    .ok_or(WalkError::MissingExpectedDynstrSection)?,
+            };
             let mut dyn_deps = Vec::new();
+            let mut soname = None;
+            let mut rpath = Vec::new();
+            let mut runpath = Vec::new();
+            let mut filters = Vec::new();
+            let mut auxiliary_filters = Vec::new();
+            let mut audit = Vec::new();
+            let mut dependency_audit = Vec::new();
+            let mut flags = 0;
+            let mut flags_1 = 0;
+            let mut bind_now = false;
+            let mut textrel = false;
+            let mut init_fini = InitFiniEntries::default();
 
             for d in dyn_entries {
                 match d.tag32(end) {
                     None => {}
-                    Some(tag) => {
-                        if tag == elf::DT_NEEDED {
-                            let needed_string_bytes = d.string(end, dyn_strings)?;
-                            let needed_string = String::from_utf8(needed_string_bytes.to_vec())?;
-                            dyn_deps.push(needed_string.clone());
+                    Some(elf::DT_NEEDED) => {
+                        let needed_string_bytes = d.string(end, dyn_strings)?;
+                        let needed_string = String::from_utf8(needed_string_bytes.to_vec())?;
+                        // The loader ignores repeated entries, so only the first is kept
+                        if dyn_deps.contains(&needed_string) {
+                            log::debug!("Ignoring duplicate DT_NEEDED entry {}", needed_string);
+                        } else {
+                            dyn_deps.push(needed_string);
                         }
                     }
+                    Some(elf::DT_SONAME) => {
+                        let soname_bytes = d.string(end, dyn_strings)?;
+                        soname = Some(String::from_utf8_lossy(soname_bytes).into_owned());
+                    }
+                    Some(elf::DT_RPATH) => {
+                        let path_bytes = d.string(end, dyn_strings)?;
+                        rpath.extend(split_search_path(path_bytes));
+                    }
+                    Some(elf::DT_RUNPATH) => {
+                        let path_bytes = d.string(end, dyn_strings)?;
+                        runpath.extend(split_search_path(path_bytes));
+                    }
+                    Some(elf::DT_FILTER) => {
+                        let filter_bytes = d.string(end, dyn_strings)?;
+                        filters.push(String::from_utf8_lossy(filter_bytes).into_owned());
+                    }
+                    Some(elf::DT_AUXILIARY) => {
+                        let filter_bytes = d.string(end, dyn_strings)?;
+                        auxiliary_filters.push(String::from_utf8_lossy(filter_bytes).into_owned());
+                    }
+                    Some(elf::DT_AUDIT) => {
+                        let audit_bytes = d.string(end, dyn_strings)?;
+                        audit.extend(split_library_list(audit_bytes));
+                    }
+                    Some(elf::DT_DEPAUDIT) => {
+                        let audit_bytes = d.string(end, dyn_strings)?;
+                        dependency_audit.extend(split_library_list(audit_bytes));
+                    }
+                    Some(elf::DT_FLAGS) => {
+                        flags = d.d_val(end).into();
+                    }
+                    Some(elf::DT_FLAGS_1) => {
+                        flags_1 = d.d_val(end).into();
+                    }
+                    Some(elf::DT_BIND_NOW) => {
+                        bind_now = true;
+                    }
+                    Some(elf::DT_TEXTREL) => {
+                        textrel = true;
+                    }
+                    Some(elf::DT_INIT) => {
+                        init_fini.init = Some(d.d_val(end).into());
+                    }
+                    Some(elf::DT_FINI) => {
+                        init_fini.fini = Some(d.d_val(end).into());
+                    }
+                    Some(elf::DT_INIT_ARRAY) => {
+                        init_fini.init_array.0 = d.d_val(end).into();
+                    }
+                    Some(elf::DT_INIT_ARRAYSZ) => {
+                        init_fini.init_array.1 = d.d_val(end).into();
+                    }
+                    Some(elf::DT_FINI_ARRAY) => {
+                        init_fini.fini_array.0 = d.d_val(end).into();
+                    }
+                    Some(elf::DT_FINI_ARRAYSZ) => {
+                        init_fini.fini_array.1 = d.d_val(end).into();
+                    }
+                    Some(_) => {}
                 }
             }
 
             let mut undef_symbols = Vec::new();
             let mut def_symbols = Vec::new();
-            let (dynsym_sec_idx, _dynsym_sec) = sec_table
-                .section_by_name(end, ".dynsym".as_bytes())
-                .ok_or(WalkError::MissingExpectedDynsymSection)?;
-            let dyn_symtab =
-                sec_table.symbol_table_by_index(end, bytes, SectionIndex(dynsym_sec_idx))?;
-            for sym in dyn_symtab.symbols() {
-                let sym_name = VersionedSymbol::new::<Elf>(end, &dyn_strings, sym);
+            let hash_counts = hash_symbol_counts(end, bytes, obj, &segments, dyn_entries);
+            let dyn_syms = match sec_table.section_by_name(end, ".dynsym".as_bytes()) {
+                Some((dynsym_sec_idx, _dynsym_sec)) => sec_table
+                    .symbol_table_by_index(end, bytes, SectionIndex(dynsym_sec_idx))?
+                    .symbols(),
+                None => {
+                    dynamic_symbol_table::<Elf>(end, bytes, &segments, dyn_entries, &hash_counts)?
+                        .ok_or(WalkError::MissingExpectedDynsymSection)?
+                }
+            };
+            let versions = SymbolVersions::parse(end, bytes, sec_table, dyn_strings)?;
+            for (sym_idx, sym) in dyn_syms.iter().enumerate() {
+                let sym_name = VersionedSymbol::new::<Elf>(
+                    end,
+                    &dyn_strings,
+                    sym,
+                    versions.version(end, sym_idx),
+                );
                 if sym_name.name.is_empty() {
                     continue;
                 }
@@ -191,6 +1419,7 @@ where
                         symbol: sym_name,
                         type_: SymbolType::new(sym.st_type()),
                         binding: SymbolBinding::new(sym.st_bind()),
+                        visibility: SymbolVisibility::new(sym.st_visibility()),
                     };
                     undef_symbols.push(dyn_ref);
                 } else {
@@ -198,17 +1427,54 @@ where
                         symbol: sym_name,
                         type_: SymbolType::new(sym.st_type()),
                         binding: SymbolBinding::new(sym.st_bind()),
+                        visibility: SymbolVisibility::new(sym.st_visibility()),
                         size: sym.st_size(end).into(),
                         address: sym.st_value(end).into(),
+                        section: SymbolSection::new(sym.st_shndx(end)),
                     };
                     def_symbols.push(dyn_ref);
                 }
             }
 
+            let (init, fini, init_array, fini_array) = parse_init_functions(
+                end,
+                bytes,
+                obj,
+                sec_table,
+                &segments,
+                &init_fini,
+                &def_symbols,
+            )?;
+
             let dyn_data = DynamicData {
                 deps: dyn_deps,
+                soname,
+                rpath,
+                runpath,
+                filters,
+                auxiliary_filters,
+                audit,
+                dependency_audit,
                 dynamic_symbol_refs: undef_symbols,
                 provided_dynamic_symbols: def_symbols,
+                flags,
+                flags_1,
+                bind_now,
+                textrel,
+                version_definitions: versions.definitions,
+                version_requirements: versions.requirements,
+                init,
+                fini,
+                init_array,
+                fini_array,
+                dynamic_symbol_count: dyn_syms.len(),
+                hash_symbol_counts: hash_counts,
+                relocation_counts: relocation_counts(
+                    end,
+                    bytes,
+                    sec_table,
+                    dynamic_value::<Elf>(end, dyn_entries, elf::DT_JMPREL),
+                ),
             };
             Ok(BinaryType::Dynamic(dyn_data))
         }
@@ -250,6 +1516,260 @@ fn parse_segment<Elf: elf_reader::FileHeader<Endian = Endianness>>(
     }
 }
 
+/// Read the contents of a section, transparently decompressing `SHF_COMPRESSED` sections
+///
+/// Only zlib compression is supported; `SHF_COMPRESSED` cannot be combined with
+/// `SHF_ALLOC`, so this mostly affects debug information and `.comment`
+fn section_contents<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    name: &str,
+    hdr: &Elf::SectionHeader,
+) -> anyhow::Result<Cow<'data, [u8]>> {
+    let data = hdr.data(end, bytes)?;
+    let flags: u64 = hdr.sh_flags(end).into();
+    if flags & u64::from(elf::SHF_COMPRESSED) == 0 {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    let (chdr, payload) = object::pod::from_bytes::<Elf::CompressionHeader>(data)
+        .map_err(|()| WalkError::InvalidCompressionHeader(name.to_string()))?;
+    if chdr.ch_type(end) != elf::ELFCOMPRESS_ZLIB {
+        return Err(anyhow::Error::new(WalkError::UnsupportedCompression(
+            name.to_string(),
+            chdr.ch_type(end),
+        )));
+    }
+    let compressed = CompressedData {
+        format: CompressionFormat::Zlib,
+        data: payload,
+        uncompressed_size: chdr.ch_size(end).into(),
+    };
+    Ok(compressed.decompress()?)
+}
+
+/// Read the NUL-separated strings in the `.comment` section (if any)
+fn parse_comment<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> anyhow::Result<Vec<String>> {
+    match sec_table.section_by_name(end, ".comment".as_bytes()) {
+        None => Ok(Vec::new()),
+        Some((_, hdr)) => {
+            let contents = section_contents::<Elf>(end, bytes, ".comment", hdr)?;
+            Ok(split_strings(&contents))
+        }
+    }
+}
+
+/// Read the `.gnu_debuglink` section (if any): a NUL-terminated file name,
+/// padded to a multiple of four bytes, followed by the CRC-32 of the debug file
+fn parse_debug_link<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> Option<DebugLink> {
+    let (_, hdr) = sec_table.section_by_name(end, ".gnu_debuglink".as_bytes())?;
+    let contents = section_contents::<Elf>(end, bytes, ".gnu_debuglink", hdr).ok()?;
+    let name_len = contents.iter().position(|b| *b == 0)?;
+    let crc_offset = (name_len + 4) & !3;
+    let crc = contents.get(crc_offset..crc_offset + 4)?;
+    Some(DebugLink {
+        file: String::from_utf8_lossy(&contents[..name_len]).into_owned(),
+        crc: end.read_u32_bytes(crc.try_into().ok()?),
+    })
+}
+
+/// The functions and data objects defined in the static symbol table (`.symtab`)
+/// of the ELF file at `path`, whose contents are `bytes`, along with its sections
+///
+/// This is used to read the symbols of a stripped binary from its separate
+/// debug file, whose symbols have the addresses of the original binary (even
+/// though its code and data sections are empty).  The section indexes of the
+/// symbols refer to the returned sections, rather than those of the binary.
+pub fn symtab_definitions(
+    path: &Path,
+    bytes: &[u8],
+) -> anyhow::Result<(Vec<ExportedDynamicSymbol>, Vec<Section>)> {
+    match elf::FileHeader64::<Endianness>::parse(bytes) {
+        Ok(e64) => read_symtab_definitions(bytes, e64),
+        Err(_) => match elf::FileHeader32::<Endianness>::parse(bytes) {
+            Ok(e32) => read_symtab_definitions(bytes, e32),
+            Err(_) => {
+                let err = WalkError::NotAnElfFile(path.to_path_buf());
+                Err(anyhow::Error::new(err))
+            }
+        },
+    }
+}
+
+fn read_symtab_definitions<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    bytes: &[u8],
+    obj: &Elf,
+) -> anyhow::Result<(Vec<ExportedDynamicSymbol>, Vec<Section>)> {
+    let end = obj.endian()?;
+    let sec_table = obj.sections(end, bytes)?;
+    let section_strings = obj.section_strings(end, bytes, sec_table.iter().as_slice())?;
+    let sections = sec_table
+        .iter()
+        .map(|s| parse_section::<Elf>(end, &section_strings, s))
+        .collect();
+    let symtab = sec_table.symbols(end, bytes, elf::SHT_SYMTAB)?;
+    let mut defs = Vec::new();
+    for sym in symtab.symbols() {
+        let type_ = sym.st_type();
+        let is_definition = matches!(
+            type_,
+            elf::STT_FUNC | elf::STT_OBJECT | elf::STT_TLS | elf::STT_GNU_IFUNC
+        );
+        if !is_definition || sym.is_undefined(end) {
+            continue;
+        }
+        let name = match sym.name(end, symtab.strings()) {
+            Ok(name) if !name.is_empty() => name,
+            _ => continue,
+        };
+        defs.push(ExportedDynamicSymbol {
+            symbol: VersionedSymbol {
+                name: String::from_utf8_lossy(name).into_owned(),
+                version: None,
+            },
+            type_: SymbolType::new(type_),
+            binding: SymbolBinding::new(sym.st_bind()),
+            visibility: SymbolVisibility::new(sym.st_visibility()),
+            size: sym.st_size(end).into(),
+            address: sym.st_value(end).into(),
+            section: SymbolSection::new(sym.st_shndx(end)),
+        });
+    }
+    Ok((defs, sections))
+}
+
+/// Split a table of NUL-terminated strings into its (non-empty) strings
+fn split_strings(data: &[u8]) -> Vec<String> {
+    data.split(|b| *b == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| String::from_utf8_lossy(s).into_owned())
+        .collect()
+}
+
+// These are not (yet) exported by the `object` crate
+const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+
+/// Find the `GNU_PROPERTY_*_FEATURE_1_AND` bits in the `NT_GNU_PROPERTY_TYPE_0` note, if any
+///
+/// The property array is a sequence of (type, size, data) entries, where each
+/// data payload is padded to the natural word size of the binary.
+fn parse_feature_1_and<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> anyhow::Result<Option<u32>> {
+    let align = if obj.is_class_32() { 4 } else { 8 };
+    for hdr in sec_table.iter() {
+        let mut notes = match hdr.notes(end, bytes)? {
+            None => continue,
+            Some(notes) => notes,
+        };
+        while let Some(note) = notes.next()? {
+            if note.name() != b"GNU" || note.n_type(end) != NT_GNU_PROPERTY_TYPE_0 {
+                continue;
+            }
+
+            let mut desc = note.desc();
+            while desc.len() >= 8 {
+                let pr_type = end.read_u32_bytes(desc[0..4].try_into()?);
+                let pr_datasz = end.read_u32_bytes(desc[4..8].try_into()?) as usize;
+                let data = desc.get(8..8usize.saturating_add(pr_datasz)).unwrap_or(&[]);
+                if (pr_type == GNU_PROPERTY_X86_FEATURE_1_AND
+                    || pr_type == GNU_PROPERTY_AARCH64_FEATURE_1_AND)
+                    && data.len() >= 4
+                {
+                    return Ok(Some(end.read_u32_bytes(data[0..4].try_into()?)));
+                }
+                let padded = pr_datasz.saturating_add(align - 1) / align * align;
+                desc = desc.get(8usize.saturating_add(padded)..).unwrap_or(&[]);
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Find the build ID (`NT_GNU_BUILD_ID`) of the binary, rendered in hex
+///
+/// The build ID is in the `.note.gnu.build-id` section, but it is also
+/// found through the `PT_NOTE` segments if the section headers are stripped
+fn parse_build_id<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> anyhow::Result<Option<String>> {
+    let mut note_iters = Vec::new();
+    for hdr in sec_table.iter() {
+        note_iters.extend(hdr.notes(end, bytes)?);
+    }
+    if sec_table.is_empty() {
+        for phdr in obj.program_headers(end, bytes)? {
+            note_iters.extend(phdr.notes(end, bytes)?);
+        }
+    }
+    for mut notes in note_iters {
+        while let Some(note) = notes.next()? {
+            if note.name() == b"GNU" && note.n_type(end) == elf::NT_GNU_BUILD_ID {
+                let hex = note.desc().iter().map(|b| format!("{:02x}", b)).collect();
+                return Ok(Some(hex));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Collect the process information from the `CORE` notes of a core dump
+///
+/// Core dumps have no section headers, so the notes are found through the
+/// `PT_NOTE` segments
+fn parse_core<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+) -> anyhow::Result<CoreInfo> {
+    let word_size = if obj.is_class_32() { 4 } else { 8 };
+    let mut info = CoreInfo::default();
+    for phdr in obj.program_headers(end, bytes)? {
+        let mut notes = match phdr.notes(end, bytes)? {
+            None => continue,
+            Some(notes) => notes,
+        };
+        while let Some(note) = notes.next()? {
+            if note.name() != b"CORE" {
+                continue;
+            }
+            match note.n_type(end) {
+                elf::NT_PRPSINFO => {
+                    let (executable, arguments) = core_dump::prpsinfo_command(note.desc());
+                    info.executable = executable;
+                    info.arguments = arguments;
+                }
+                // There is a status note for each thread; the first is the one that crashed
+                elf::NT_PRSTATUS if info.signal.is_none() => {
+                    info.signal = core_dump::prstatus_signal(end, note.desc());
+                }
+                elf::NT_FILE => {
+                    info.mapped_files =
+                        core_dump::file_mappings(end, note.desc(), word_size).unwrap_or_default();
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(info)
+}
+
 fn summarize_elf<Elf: elf_reader::FileHeader<Endian = Endianness>>(
     f: &Path,
     bytes: &[u8],
@@ -262,6 +1782,17 @@ fn summarize_elf<Elf: elf_reader::FileHeader<Endian = Endianness>>(
     let parsed_segments = obj.program_headers(end, bytes)?;
 
     let deps = analyze_dependencies(bytes, obj, &sec_table)?;
+    let feature_1_and = parse_feature_1_and(end, bytes, obj, &sec_table)?;
+    // The comment is purely informational, so a corrupt (or unsupported
+    // compressed) `.comment` section should not prevent summarizing the binary
+    let comment = parse_comment(end, bytes, &sec_table).unwrap_or_default();
+    let build_id = parse_build_id(end, bytes, obj, &sec_table).unwrap_or_default();
+    let debug_link = parse_debug_link(end, bytes, &sec_table);
+    let core = if obj.e_type(end) == elf::ET_CORE {
+        Some(parse_core(end, bytes, obj)?)
+    } else {
+        None
+    };
     let bs = ElfSummary {
         endianness: if obj.is_little_endian() {
             Endianness::Little
@@ -270,20 +1801,603 @@ fn summarize_elf<Elf: elf_reader::FileHeader<Endian = Endianness>>(
         },
         bit_size: if obj.is_class_32() { 32 } else { 64 },
         filename: PathBuf::from(f),
+        file_type: obj.e_type(end),
+        machine: obj.e_machine(end),
+        entry: obj.e_entry(end).into(),
         binary_type: deps,
         sections: sec_table.iter().map(|s| parse_section::<Elf>(end, &section_header_strings, s)).collect(),
         segments: parsed_segments.iter().map(|s| parse_segment::<Elf>(end, s)).collect(),
+        feature_1_and,
+        comment,
+        build_id,
+        debug_link,
+        debug_file: None,
+        debug_symbols: Vec::new(),
+        debug_sections: Vec::new(),
+        sha256: String::new(),
+        core,
+        modified: None,
     };
     Ok(bs)
 }
 
+/// Summarize the binary at the given path
+///
+/// The path can also name a member of a tar archive (e.g., `image.tar:/usr/bin/foo`),
+/// in which case the member is read without extracting the archive
 pub fn summarize_path(path: &PathBuf) -> anyhow::Result<ElfSummary> {
-    let bytes = fs::read(path)?;
+    timings::count_file_parsed();
+    // This is read first, so that a change while the file is being read is
+    // noticed later
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let bytes = archive::read_binary(path)?;
+    let mut summary = summarize_bytes(path, bytes.as_slice())?;
+    summary.modified = modified;
+    Ok(summary)
+}
+
+/// Summarize a binary that is already in memory
+///
+/// The `name` is only used as the filename of the summary (and in errors), so
+/// it does not need to exist.  Malformed inputs must produce an error rather
+/// than a panic, since this is run on untrusted binaries (including every
+/// library found on the search path)
+pub fn summarize_bytes(name: impl AsRef<Path>, bytes: &[u8]) -> anyhow::Result<ElfSummary> {
+    let path = name.as_ref();
+    let mut summary = match object::FileKind::parse(bytes) {
+        Ok(object::FileKind::Pe32 | object::FileKind::Pe64) => pe::summarize_pe(path, bytes)?,
+        _ => match elf::FileHeader64::<Endianness>::parse(bytes) {
+            Ok(e64) => summarize_elf(path, bytes, e64)?,
+            Err(_) => match elf::FileHeader32::<Endianness>::parse(bytes) {
+                Ok(e32) => summarize_elf(path, bytes, e32)?,
+                Err(_) => {
+                    let err = WalkError::NotAnElfFile(path.to_path_buf());
+                    return Err(anyhow::Error::new(err));
+                }
+            },
+        },
+    };
+    summary.sha256 = Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(summary)
+}
+
+fn find_section_contents<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    bytes: &[u8],
+    obj: &Elf,
+    name: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let end = obj.endian()?;
+    let sec_table = obj.sections(end, bytes)?;
+    match sec_table.section_by_name(end, name.as_bytes()) {
+        None => Ok(None),
+        Some((_, hdr)) => {
+            let contents = section_contents::<Elf>(end, bytes, name, hdr)?;
+            Ok(Some(contents.into_owned()))
+        }
+    }
+}
+
+/// Read the (decompressed) contents of the named section, or `None` if there is no such section
+pub fn read_section(path: &Path, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    let bytes = archive::read_binary(path)?;
     match elf::FileHeader64::<Endianness>::parse(bytes.as_slice()) {
-        Ok(e64) => summarize_elf(path.as_path(), bytes.as_slice(), e64),
+        Ok(e64) => find_section_contents(bytes.as_slice(), e64, name),
         Err(_) => match elf::FileHeader32::<Endianness>::parse(bytes.as_slice()) {
-            Ok(e32) => summarize_elf(path.as_path(), bytes.as_slice(), e32),
-            Err(_) => unimplemented!(),
+            Ok(e32) => find_section_contents(bytes.as_slice(), e32, name),
+            Err(_) => {
+                let err = WalkError::NotAnElfFile(path.to_path_buf());
+                Err(anyhow::Error::new(err))
+            }
+        },
+    }
+}
+
+/// The raw contents of the dynamic string table (see `analyze_dependencies`)
+fn dynamic_string_data<'data, Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &'data [u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<'data, Elf>,
+) -> anyhow::Result<Option<&'data [u8]>> {
+    if let Some((_, hdr)) = sec_table.section_by_name(end, ".dynstr".as_bytes()) {
+        return Ok(Some(hdr.data(end, bytes)?));
+    }
+    let dyn_entries = match dynamic_entries(end, bytes, obj, sec_table)? {
+        None => return Ok(None),
+        Some(dyn_entries) => dyn_entries,
+    };
+    let segments: Vec<Segment> = obj
+        .program_headers(end, bytes)?
+        .iter()
+        .map(|s| parse_segment::<Elf>(end, s))
+        .collect();
+    let size = dynamic_value::<Elf>(end, dyn_entries, elf::DT_STRSZ).unwrap_or(0);
+    Ok(
+        dynamic_table_data::<Elf>(end, bytes, &segments, dyn_entries, elf::DT_STRTAB)
+            .and_then(|data| data.get(..size as usize)),
+    )
+}
+
+/// Render a note descriptor as text
+///
+/// The ABI tag is decoded into the minimum kernel version, and the build ID is
+/// shown in hex (as in `file` and `readelf -n`)
+fn render_note_descriptor(end: Endianness, owner: &[u8], type_: u32, desc: &[u8]) -> String {
+    let hex = || {
+        desc.iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    };
+    if owner == b"GNU" {
+        match type_ {
+            elf::NT_GNU_BUILD_ID => return hex(),
+            elf::NT_GNU_ABI_TAG if desc.len() >= 16 => {
+                let words: Vec<u32> = desc[0..16]
+                    .chunks_exact(4)
+                    .map(|w| end.read_u32_bytes([w[0], w[1], w[2], w[3]]))
+                    .collect();
+                let os = match words[0] {
+                    elf::ELF_NOTE_OS_LINUX => String::from("Linux"),
+                    elf::ELF_NOTE_OS_GNU => String::from("Hurd"),
+                    elf::ELF_NOTE_OS_SOLARIS2 => String::from("Solaris"),
+                    elf::ELF_NOTE_OS_FREEBSD => String::from("FreeBSD"),
+                    os => format!("OS {}", os),
+                };
+                return format!("{} {}.{}.{}", os, words[1], words[2], words[3]);
+            }
+            _ => {}
+        }
+    }
+    let text = desc.strip_suffix(&[0]).unwrap_or(desc);
+    if !text.is_empty() && text.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        String::from_utf8_lossy(text).into_owned()
+    } else {
+        hex()
+    }
+}
+
+/// Append the notes from `notes` (which are all in `section`) to `res`
+fn collect_notes<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    section: &str,
+    mut notes: elf_reader::NoteIterator<Elf>,
+    res: &mut Vec<NoteDescriptor>,
+) -> anyhow::Result<()> {
+    while let Some(note) = notes.next()? {
+        let type_ = note.n_type(end);
+        res.push(NoteDescriptor {
+            section: section.to_string(),
+            owner: String::from_utf8_lossy(note.name()).into_owned(),
+            type_,
+            descriptor: render_note_descriptor(end, note.name(), type_, note.desc()),
+        });
+    }
+    Ok(())
+}
+
+/// Collect the notes in the `SHT_NOTE` sections, or in the `PT_NOTE` segments if
+/// there are no section headers
+fn parse_notes<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    end: Elf::Endian,
+    bytes: &[u8],
+    obj: &Elf,
+    sec_table: &elf_reader::SectionTable<Elf>,
+) -> anyhow::Result<Vec<NoteDescriptor>> {
+    let mut res = Vec::new();
+    let section_strings = obj.section_strings(end, bytes, sec_table.iter().as_slice())?;
+    for hdr in sec_table.iter() {
+        if let Some(notes) = hdr.notes(end, bytes)? {
+            let section = parse_section::<Elf>(end, &section_strings, hdr);
+            collect_notes::<Elf>(end, &section.name, notes, &mut res)?;
+        }
+    }
+
+    if sec_table.is_empty() {
+        for phdr in obj.program_headers(end, bytes)? {
+            if let Some(notes) = phdr.notes(end, bytes)? {
+                collect_notes::<Elf>(end, "PT_NOTE", notes, &mut res)?;
+            }
+        }
+    }
+    Ok(res)
+}
+
+fn find_strings<Elf: elf_reader::FileHeader<Endian = Endianness>>(
+    f: &Path,
+    bytes: &[u8],
+    obj: &Elf,
+) -> anyhow::Result<BinaryStrings> {
+    let end = obj.endian()?;
+    let sec_table = obj.sections(end, bytes)?;
+    let dynstr = dynamic_string_data(end, bytes, obj, &sec_table)?;
+    Ok(BinaryStrings {
+        file: PathBuf::from(f),
+        dynstr: dynstr.map(split_strings).unwrap_or_default(),
+        comment: parse_comment(end, bytes, &sec_table)?,
+        notes: parse_notes(end, bytes, obj, &sec_table)?,
+    })
+}
+
+/// Read the strings from the dynamic string table, `.comment`, and notes of the binary
+pub fn read_strings(path: &Path) -> anyhow::Result<BinaryStrings> {
+    let bytes = archive::read_binary(path)?;
+    match elf::FileHeader64::<Endianness>::parse(bytes.as_slice()) {
+        Ok(e64) => find_strings(path, bytes.as_slice(), e64),
+        Err(_) => match elf::FileHeader32::<Endianness>::parse(bytes.as_slice()) {
+            Ok(e32) => find_strings(path, bytes.as_slice(), e32),
+            Err(_) => {
+                let err = WalkError::NotAnElfFile(path.to_path_buf());
+                Err(anyhow::Error::new(err))
+            }
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DYN_OFFSET: usize = 176;
+    const HASH_OFFSET: usize = 272;
+    const SYMTAB_OFFSET: usize = 288;
+    const STRTAB_OFFSET: usize = 312;
+    const STRINGS: &[u8] = b"\0libc.so.6\0";
+
+    fn put_u16(image: &mut [u8], offset: usize, value: u16) {
+        image[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(image: &mut [u8], offset: usize, value: u32) {
+        image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u64(image: &mut [u8], offset: usize, value: u64) {
+        image[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// A minimal 64 bit little endian shared library without section headers
+    ///
+    /// It has a `PT_LOAD` segment covering the whole file and a `PT_DYNAMIC`
+    /// segment with a single `DT_NEEDED` entry, along with the string, hash, and
+    /// symbol tables (holding only the null symbol) that it requires
+    fn minimal_elf() -> Vec<u8> {
+        let size = STRTAB_OFFSET + STRINGS.len();
+        let mut image = vec![0; size];
+        image[0..4].copy_from_slice(b"\x7fELF");
+        image[4] = elf::ELFCLASS64;
+        image[5] = elf::ELFDATA2LSB;
+        image[6] = elf::EV_CURRENT;
+        put_u16(&mut image, 16, elf::ET_DYN);
+        put_u16(&mut image, 18, elf::EM_X86_64);
+        put_u32(&mut image, 20, elf::EV_CURRENT.into());
+        put_u64(&mut image, 32, 64);
+        put_u16(&mut image, 52, 64);
+        put_u16(&mut image, 54, 56);
+        put_u16(&mut image, 56, 2);
+        put_u16(&mut image, 58, 64);
+
+        // PT_LOAD covering the whole file at address zero
+        put_u32(&mut image, 64, elf::PT_LOAD);
+        put_u32(&mut image, 68, elf::PF_R);
+        put_u64(&mut image, 64 + 32, size as u64);
+        put_u64(&mut image, 64 + 40, size as u64);
+        put_u64(&mut image, 64 + 48, 0x1000);
+
+        // PT_DYNAMIC
+        let dyn_entries = [
+            (elf::DT_NEEDED, 1),
+            (elf::DT_STRTAB, STRTAB_OFFSET as u64),
+            (elf::DT_STRSZ, STRINGS.len() as u64),
+            (elf::DT_HASH, HASH_OFFSET as u64),
+            (elf::DT_SYMTAB, SYMTAB_OFFSET as u64),
+            (elf::DT_NULL, 0),
+        ];
+        put_u32(&mut image, 120, elf::PT_DYNAMIC);
+        put_u32(&mut image, 124, elf::PF_R | elf::PF_W);
+        put_u64(&mut image, 120 + 8, DYN_OFFSET as u64);
+        put_u64(&mut image, 120 + 16, DYN_OFFSET as u64);
+        put_u64(&mut image, 120 + 32, (dyn_entries.len() * 16) as u64);
+        put_u64(&mut image, 120 + 40, (dyn_entries.len() * 16) as u64);
+        put_u64(&mut image, 120 + 48, 8);
+        for (idx, (tag, value)) in dyn_entries.iter().enumerate() {
+            put_u64(&mut image, DYN_OFFSET + idx * 16, u64::from(*tag));
+            put_u64(&mut image, DYN_OFFSET + idx * 16 + 8, *value);
+        }
+
+        // A System V hash table with one bucket and one (null) symbol
+        put_u32(&mut image, HASH_OFFSET, 1);
+        put_u32(&mut image, HASH_OFFSET + 4, 1);
+
+        image[STRTAB_OFFSET..].copy_from_slice(STRINGS);
+        image
+    }
+
+    fn summarize(image: &[u8]) -> anyhow::Result<ElfSummary> {
+        summarize_bytes("test.so", image)
+    }
+
+    #[test]
+    fn minimal_elf_is_summarized() {
+        let summary = summarize(&minimal_elf()).unwrap();
+        match summary.binary_type {
+            BinaryType::Static => panic!("Expected a dynamically-linked binary"),
+            BinaryType::Dynamic(dyn_data) => {
+                assert_eq!(dyn_data.deps, vec![String::from("libc.so.6")]);
+                assert_eq!(dyn_data.dynamic_symbol_count, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn in_memory_summary_is_named_and_hashed() {
+        let image = minimal_elf();
+        let summary = summarize(&image).unwrap();
+        assert_eq!(summary.filename, PathBuf::from("test.so"));
+        assert_eq!(summary.sha256.len(), 64);
+        assert_eq!(summary.sha256, summarize(&image).unwrap().sha256);
+    }
+
+    #[test]
+    fn truncated_inputs_do_not_panic() {
+        let image = minimal_elf();
+        for len in 0..image.len() {
+            let _ = summarize(&image[..len]);
+        }
+    }
+
+    #[test]
+    fn corrupted_bytes_do_not_panic() {
+        let image = minimal_elf();
+        for offset in 0..image.len() {
+            for value in [0x00, 0x01, 0x7f, 0x80, 0xff] {
+                let mut corrupted = image.clone();
+                corrupted[offset] = value;
+                let _ = summarize(&corrupted);
+            }
+        }
+    }
+
+    #[test]
+    fn extreme_dynamic_values_do_not_panic() {
+        let image = minimal_elf();
+        // Point each dynamic entry (and the segment fields) at the ends of the address space
+        let segment_fields = [64 + 8, 64 + 16, 64 + 32, 120 + 8, 120 + 32];
+        let fields = (0..6)
+            .map(|idx| DYN_OFFSET + idx * 16 + 8)
+            .chain(segment_fields);
+        for field in fields {
+            for value in [u64::MAX, u64::MAX - 7, 1 << 63, 1 << 32] {
+                let mut corrupted = image.clone();
+                put_u64(&mut corrupted, field, value);
+                let _ = summarize(&corrupted);
+            }
+        }
+    }
+
+    /// The minimal image with a section header table holding the null section,
+    /// an allocated `SHT_RELA` section of one entry at `offset` (which may be
+    /// outside of the file), and the section name table
+    fn with_rela_section(offset: u64) -> Vec<u8> {
+        const NAMES: &[u8] = b"\0.rela.dyn\0.shstrtab\0";
+        let mut image = minimal_elf();
+        let names = image.len();
+        image.extend_from_slice(NAMES);
+        let shoff = image.len();
+        image.resize(shoff + 3 * 64, 0);
+        put_u64(&mut image, 40, shoff as u64);
+        put_u16(&mut image, 60, 3);
+        put_u16(&mut image, 62, 2);
+
+        let rela = shoff + 64;
+        put_u32(&mut image, rela, 1);
+        put_u32(&mut image, rela + 4, elf::SHT_RELA);
+        put_u64(&mut image, rela + 8, u64::from(elf::SHF_ALLOC));
+        put_u64(&mut image, rela + 24, offset);
+        put_u64(&mut image, rela + 32, 24);
+        put_u64(&mut image, rela + 56, 24);
+
+        let shstrtab = shoff + 128;
+        put_u32(&mut image, shstrtab, 11);
+        put_u32(&mut image, shstrtab + 4, elf::SHT_STRTAB);
+        put_u64(&mut image, shstrtab + 24, names as u64);
+        put_u64(&mut image, shstrtab + 32, NAMES.len() as u64);
+        image
+    }
+
+    #[test]
+    fn malformed_rela_section_does_not_fail_the_summary() {
+        let summary = summarize(&with_rela_section(0x7fff_ffff)).unwrap();
+        match summary.binary_type {
+            BinaryType::Static => panic!("Expected a dynamically-linked binary"),
+            BinaryType::Dynamic(dyn_data) => {
+                assert_eq!(dyn_data.deps, vec![String::from("libc.so.6")]);
+                assert!(dyn_data.init_array.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_relocation_sections_are_not_counted() {
+        let counts = |image: &[u8]| match summarize(image).unwrap().binary_type {
+            BinaryType::Static => panic!("Expected a dynamically-linked binary"),
+            BinaryType::Dynamic(dyn_data) => dyn_data.relocation_counts,
+        };
+        // The same section within the file (over the ELF header) is counted
+        let valid = counts(&with_rela_section(0));
+        assert_eq!((valid.plt, valid.dynamic), (0, 1));
+        let malformed = counts(&with_rela_section(0x7fff_ffff));
+        assert_eq!((malformed.plt, malformed.dynamic), (0, 0));
+    }
+
+    /// The minimal image for aarch64, with its `PT_DYNAMIC` segment turned into
+    /// a second `PT_LOAD` segment at `vaddr` with the given alignment
+    fn aarch64_two_segments(vaddr: u64, alignment: u64) -> Vec<u8> {
+        let mut image = minimal_elf();
+        put_u16(&mut image, 18, elf::EM_AARCH64);
+        put_u32(&mut image, 120, elf::PT_LOAD);
+        put_u64(&mut image, 120 + 16, vaddr);
+        put_u64(&mut image, 120 + 48, alignment);
+        image
+    }
+
+    #[test]
+    fn segment_alignment_uses_the_segment_page_size() {
+        // The second segment is at a 64K boundary plus its offset
+        let aligned = aarch64_two_segments(0x10000 + DYN_OFFSET as u64, 0x10000);
+        let summary = summarize(&aligned).unwrap();
+        assert_eq!(summary.segment_warnings(), Vec::<String>::new());
+
+        // This would be fine with 4K pages, but not with 64K pages
+        let misaligned = aarch64_two_segments(0x11000 + DYN_OFFSET as u64, 0x10000);
+        let summary = summarize(&misaligned).unwrap();
+        assert_eq!(summary.segment_warnings().len(), 1);
+
+        let odd = aarch64_two_segments(0x10000 + DYN_OFFSET as u64, 0x3000);
+        let summary = summarize(&odd).unwrap();
+        assert!(summary.segment_warnings()[0].contains("not a power of two"));
+    }
+
+    #[test]
+    fn non_elf_inputs_are_rejected() {
+        for image in [&b""[..], b"\x7fELF", b"#!/bin/sh\necho hello\n"] {
+            assert!(summarize(image).is_err());
+        }
+    }
+
+    fn section(name: &str, type_: u32, flags: u32) -> Section {
+        Section {
+            name: String::from(name),
+            address: 0,
+            alignment: 0,
+            offset: 0,
+            size: 0,
+            type_,
+            flags: u64::from(flags),
+        }
+    }
+
+    fn definition(
+        binding: SymbolBinding,
+        type_: SymbolType,
+        section: SymbolSection,
+    ) -> ExportedDynamicSymbol {
+        ExportedDynamicSymbol {
+            symbol: VersionedSymbol {
+                name: String::from("sym"),
+                version: None,
+            },
+            type_,
+            binding,
+            visibility: SymbolVisibility::Default,
+            size: 0,
+            address: 0,
+            section,
+        }
+    }
+
+    #[test]
+    fn debug_file_symbols_use_the_sections_of_the_debug_file() {
+        let mut summary = summarize(&minimal_elf()).unwrap();
+        summary.sections = vec![
+            section("", elf::SHT_NULL, 0),
+            section(".data", elf::SHT_PROGBITS, elf::SHF_ALLOC | elf::SHF_WRITE),
+        ];
+        summary.debug_sections = vec![
+            section("", elf::SHT_NULL, 0),
+            section(
+                ".text",
+                elf::SHT_NOBITS,
+                elf::SHF_ALLOC | elf::SHF_EXECINSTR,
+            ),
+        ];
+        assert_eq!(summary.section_name(SymbolSection::Section(1)), ".data");
+        assert_eq!(summary.section_name(SymbolSection::DebugFile(1)), ".text");
+        assert_eq!(summary.section_name(SymbolSection::DebugFile(2)), "[2]");
+
+        let func = definition(
+            SymbolBinding::Global,
+            SymbolType::Func,
+            SymbolSection::DebugFile(1),
+        );
+        assert_eq!(summary.nm_letter(&func), 'T');
+    }
+
+    #[test]
+    fn nm_letters_of_definitions() {
+        use SymbolBinding::{Global, Local, Weak};
+        use SymbolType::{Func, Ifunc, Object, Tls};
+
+        let mut summary = summarize(&minimal_elf()).unwrap();
+        summary.sections = vec![
+            section("", elf::SHT_NULL, 0),
+            section(
+                ".text",
+                elf::SHT_PROGBITS,
+                elf::SHF_ALLOC | elf::SHF_EXECINSTR,
+            ),
+            section(".data", elf::SHT_PROGBITS, elf::SHF_ALLOC | elf::SHF_WRITE),
+            section(".bss", elf::SHT_NOBITS, elf::SHF_ALLOC | elf::SHF_WRITE),
+            section(".rodata", elf::SHT_PROGBITS, elf::SHF_ALLOC),
+        ];
+        let cases = [
+            (Global, Func, SymbolSection::Section(1), 'T'),
+            (Global, Object, SymbolSection::Section(2), 'D'),
+            (Global, Object, SymbolSection::Section(3), 'B'),
+            (Global, Object, SymbolSection::Section(4), 'R'),
+            (Weak, Func, SymbolSection::Section(1), 'W'),
+            (Weak, Object, SymbolSection::Section(2), 'V'),
+            (Weak, Tls, SymbolSection::Section(3), 'V'),
+            (Global, Ifunc, SymbolSection::Section(1), 'i'),
+            (Global, Object, SymbolSection::Absolute, 'A'),
+            (Global, Object, SymbolSection::Common, 'C'),
+            (Local, Func, SymbolSection::Section(1), 't'),
+            (Local, Object, SymbolSection::Section(2), 'd'),
+            (Local, Object, SymbolSection::Section(3), 'b'),
+            (Local, Object, SymbolSection::Section(4), 'r'),
+            (Local, Object, SymbolSection::Absolute, 'a'),
+            // Without a section header, the type of the symbol decides
+            (Global, Func, SymbolSection::Section(9), 'T'),
+            (Global, Object, SymbolSection::Section(9), 'D'),
+        ];
+        for (binding, type_, section, letter) in cases {
+            let sym = definition(binding, type_, section);
+            assert_eq!(
+                summary.nm_letter(&sym),
+                letter,
+                "{:?} {:?} in {:?}",
+                binding,
+                type_,
+                section
+            );
+        }
+    }
+
+    #[test]
+    fn nm_letters_of_references() {
+        use SymbolBinding::{Global, Weak};
+        use SymbolType::{Func, NoType, Object};
+
+        let cases = [
+            (Global, Func, 'U'),
+            (Global, Object, 'U'),
+            (Weak, Func, 'w'),
+            (Weak, NoType, 'w'),
+            (Weak, Object, 'v'),
+        ];
+        for (binding, type_, letter) in cases {
+            let sym_ref = DynamicSymbolReference {
+                symbol: VersionedSymbol {
+                    name: String::from("sym"),
+                    version: None,
+                },
+                type_,
+                binding,
+                visibility: SymbolVisibility::Default,
+            };
+            assert_eq!(sym_ref.nm_letter(), letter, "{:?} {:?}", binding, type_);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Wall time accounting for the phases of an analysis (for `--timings`)
+//!
+//! The totals are global so that the phases can be timed wherever they run
+//! without threading a recorder through every renderer.  They are always
+//! collected (the cost is negligible next to parsing a binary) and are only
+//! reported if requested.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+
+#[derive(Clone, Copy)]
+pub enum Phase {
+    /// Summarizing the input binaries
+    Summarize,
+    /// Finding the dependency closure (which includes summarizing each library)
+    Dependencies,
+    /// Resolving the symbols referenced by the inputs
+    Symbols,
+}
+
+static PHASE_NANOS: [AtomicU64; 3] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+
+static FILES_PARSED: AtomicUsize = AtomicUsize::new(0);
+
+/// Run `f`, adding the time it takes to the total for `phase`
+pub fn time<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let res = f();
+    let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+    PHASE_NANOS[phase as usize].fetch_add(nanos, Ordering::Relaxed);
+    res
+}
+
+/// Record that a file was parsed (whether or not it turned out to be a valid binary)
+pub fn count_file_parsed() {
+    FILES_PARSED.fetch_add(1, Ordering::Relaxed);
+}
+
+fn millis(phase: Phase) -> f64 {
+    PHASE_NANOS[phase as usize].load(Ordering::Relaxed) as f64 / 1_000_000.0
+}
+
+/// Print the time spent in each phase to stderr (so that it does not interfere
+/// with machine-readable output)
+pub fn report() {
+    eprintln!("Timings:");
+    eprintln!(
+        "  summarize_path:       {:10.3} ms",
+        millis(Phase::Summarize)
+    );
+    eprintln!(
+        "  resolve_dependencies: {:10.3} ms",
+        millis(Phase::Dependencies)
+    );
+    eprintln!("  resolve_symbols:      {:10.3} ms", millis(Phase::Symbols));
+    eprintln!("  Files parsed: {}", FILES_PARSED.load(Ordering::Relaxed));
+}
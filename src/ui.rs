@@ -1,3 +1,4 @@
 pub mod app;
 pub mod crossterm;
 pub mod draw;
+pub mod theme;
@@ -1,16 +1,24 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tui::widgets::{ListState, TableState};
 
-use crate::resolve_symbols::resolve_symbols;
+use crate::dependencies;
+use crate::json;
+use crate::options::{DemangleStyle, SymbolColumn};
+use crate::resolve_symbols::{diagnose_unresolved, resolve_symbols, satisfies, UnresolvedReason};
 use crate::summarize;
+use crate::ui::theme::Theme;
 
 #[derive(Copy, Clone)]
 pub enum InfoTabLabels {
     Overview,
     DynamicDependencies,
     DefinedDynamicSymbols,
+    InitFini,
+    Sections,
 }
 
 impl std::fmt::Display for InfoTabLabels {
@@ -25,10 +33,81 @@ impl std::fmt::Display for InfoTabLabels {
             InfoTabLabels::DefinedDynamicSymbols => {
                 write!(f, "Defined Dynamic Symbols")
             }
+            InfoTabLabels::InitFini => {
+                write!(f, "Init/Fini")
+            }
+            InfoTabLabels::Sections => {
+                write!(f, "Sections")
+            }
         }
     }
 }
 
+/// An action that can be run from the command palette
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Command {
+    ToggleFilterExports,
+    ToggleOnlyUnresolved,
+    ToggleSubtree,
+    LookUpAddress,
+    Export,
+    Reload,
+    Quit,
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Command::ToggleFilterExports => write!(f, "Toggle showing only exported symbols (t)"),
+            Command::ToggleOnlyUnresolved => write!(f, "Toggle showing only unresolved (u)"),
+            Command::ToggleSubtree => write!(f, "Collapse or expand the selected library (Space)"),
+            Command::LookUpAddress => write!(f, "Look up an address (:)"),
+            Command::Export => write!(f, "Export the selected binary as JSON (e)"),
+            Command::Reload => write!(f, "Reload the binaries from disk (r)"),
+            Command::Quit => write!(f, "Quit (Ctrl-q)"),
+        }
+    }
+}
+
+const COMMANDS: [Command; 7] = [
+    Command::ToggleFilterExports,
+    Command::ToggleOnlyUnresolved,
+    Command::ToggleSubtree,
+    Command::LookUpAddress,
+    Command::Export,
+    Command::Reload,
+    Command::Quit,
+];
+
+/// True if the characters of `query` appear in `label` in order (ignoring case)
+fn fuzzy_matches(query: &str, label: &str) -> bool {
+    let mut label_chars = label.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| label_chars.any(|c| c == q))
+}
+
+/// The state of the command palette while it is open
+#[derive(Default)]
+pub struct CommandPalette {
+    /// The text typed to narrow down the commands
+    pub query: String,
+    /// The index of the selected command among the `matches`
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// The commands matching the query, in a fixed order
+    pub fn matches(&self) -> Vec<Command> {
+        COMMANDS
+            .iter()
+            .copied()
+            .filter(|c| fuzzy_matches(&self.query, &c.to_string()))
+            .collect()
+    }
+}
+
 /// The state of the tab bar for a *single* binary
 ///
 /// Each binary has its own state
@@ -46,12 +125,14 @@ impl TabState {
                 InfoTabLabels::Overview,
                 InfoTabLabels::DynamicDependencies,
                 InfoTabLabels::DefinedDynamicSymbols,
+                InfoTabLabels::InitFini,
+                InfoTabLabels::Sections,
             ],
             selected_tab: 0,
         }
     }
 
-    fn selected_label(&self) -> InfoTabLabels {
+    pub fn selected_label(&self) -> InfoTabLabels {
         self.tab_labels[self.selected_tab]
     }
 }
@@ -91,6 +172,8 @@ pub struct BinaryUIState {
     pub tab_state: TabState,
     pub defined_dynamic_table_state: TableState,
     pub dynamic_reference_table_state: TableState,
+    pub init_fini_table_state: TableState,
+    pub sections_table_state: TableState,
 }
 
 impl BinaryUIState {
@@ -99,20 +182,437 @@ impl BinaryUIState {
             tab_state: TabState::new(),
             defined_dynamic_table_state: TableState::default(),
             dynamic_reference_table_state: TableState::default(),
+            init_fini_table_state: TableState::default(),
+            sections_table_state: TableState::default(),
+        }
+    }
+}
+
+/// The table shown in the selected tab for a binary (if any), along with its number of rows
+///
+/// Only the unresolved references are shown if `only_unresolved` is set, and
+/// only the defined symbols of the export types if `filter_exports` is set
+fn selected_table<'s>(
+    ui_state: &'s mut BinaryUIState,
+    data: &StaticAppData,
+    elf_summ: &summarize::ElfSummary,
+    only_unresolved: bool,
+    filter_exports: bool,
+) -> Option<(&'s mut TableState, usize)> {
+    let dyn_data = match &elf_summ.binary_type {
+        summarize::BinaryType::Static => None,
+        summarize::BinaryType::Dynamic(dyn_data) => Some(dyn_data),
+    };
+    match ui_state.tab_state.selected_label() {
+        InfoTabLabels::Overview => None,
+        InfoTabLabels::DynamicDependencies => dyn_data.map(|_| {
+            (
+                &mut ui_state.dynamic_reference_table_state,
+                data.symbol_references(elf_summ, only_unresolved).len(),
+            )
+        }),
+        InfoTabLabels::DefinedDynamicSymbols => dyn_data.map(|_| {
+            (
+                &mut ui_state.defined_dynamic_table_state,
+                data.defined_symbols(elf_summ, filter_exports).len(),
+            )
+        }),
+        InfoTabLabels::InitFini => dyn_data.map(|d| {
+            (
+                &mut ui_state.init_fini_table_state,
+                d.init_fini_functions().len(),
+            )
+        }),
+        InfoTabLabels::Sections => {
+            Some((&mut ui_state.sections_table_state, elf_summ.sections.len()))
         }
     }
 }
 
-pub struct StaticAppData<'a> {
+/// How the symbol tables are shown
+pub struct SymbolTableOptions {
+    /// The columns to show in the symbol tables (empty for the defaults)
+    pub columns: Vec<SymbolColumn>,
+    /// Whether to show symbol sizes in human-readable units
+    pub human_sizes: bool,
+    /// How much detail to include in the `demangled` column
+    pub demangle_style: DemangleStyle,
+    /// The types of defined symbols to show initially (empty for all of them)
+    pub export_types: Vec<summarize::SymbolType>,
+}
+
+/// The binaries shown in the UI, along with everything derived from them
+///
+/// This owns the binaries, so it can be rebuilt when they are reloaded; the
+/// binaries in the closure are referred to by path (see [`StaticAppData::binary`])
+pub struct StaticAppData {
     pub title: String,
-    pub elf: &'a summarize::ElfSummary,
-    pub resolved_dependencies: &'a collections::BTreeMap<String, Option<summarize::ElfSummary>>,
-    pub symbol_resolutions:
-        collections::BTreeMap<summarize::VersionedSymbol, &'a summarize::ElfSummary>,
+    pub elf: summarize::ElfSummary,
+    /// The dependency closure of `elf`, including the dependencies that could not be found,
+    /// the kind of search path entry each resolved dependency was found through, and the
+    /// libraries skipped because their ELF class or byte order does not match
+    pub resolution: dependencies::Resolution,
+    /// The modification time of each binary when it was summarized (if available)
+    pub modification_times: Vec<(PathBuf, Option<SystemTime>)>,
+    /// The binary that each symbol referenced in the closure resolves to
+    pub symbol_resolutions: collections::BTreeMap<summarize::VersionedSymbol, PathBuf>,
+    /// The reasons that any symbols in the closure could not be resolved
+    pub unresolved_symbols: collections::BTreeMap<summarize::VersionedSymbol, UnresolvedReason>,
+    /// The columns to show in the symbol tables (empty for the defaults)
+    pub symbol_columns: Vec<SymbolColumn>,
+    /// Whether to show symbol sizes in human-readable units
+    pub human_sizes: bool,
+    pub demangle_style: DemangleStyle,
+    /// The types of defined symbols shown while the exports filter is enabled
+    pub export_types: Vec<summarize::SymbolType>,
+    pub theme: Theme,
+    /// The binaries in the closure that reference each symbol (the inverse of `symbol_resolutions`)
+    pub symbol_referrers: collections::BTreeMap<summarize::VersionedSymbol, Vec<PathBuf>>,
+    /// The binaries in the closure that define each symbol
+    ///
+    /// A symbol defined by more than one binary can be interposed, so which
+    /// definition is used depends on the load order
+    pub symbol_definers: collections::BTreeMap<summarize::VersionedSymbol, Vec<PathBuf>>,
+}
+
+impl StaticAppData {
+    fn new(
+        title: &str,
+        elf_summary: summarize::ElfSummary,
+        resolution: dependencies::Resolution,
+        table_options: &SymbolTableOptions,
+        theme: Theme,
+    ) -> Self {
+        let all_libs: Vec<&summarize::ElfSummary> = resolution
+            .dependencies
+            .values()
+            .filter_map(|x| x.as_ref())
+            .collect();
+        let mut resolved_syms = collections::BTreeMap::new();
+        let mut unresolved_syms = collections::BTreeMap::new();
+        let mut referrers: collections::BTreeMap<_, Vec<PathBuf>> = collections::BTreeMap::new();
+        let mut definers: collections::BTreeMap<_, Vec<PathBuf>> = collections::BTreeMap::new();
+        for bin in std::iter::once(&elf_summary).chain(all_libs.iter().copied()) {
+            match &bin.binary_type {
+                summarize::BinaryType::Static => {}
+                summarize::BinaryType::Dynamic(dyn_data) => {
+                    for sym_ref in &dyn_data.dynamic_symbol_refs {
+                        referrers
+                            .entry(sym_ref.symbol.clone())
+                            .or_default()
+                            .push(bin.filename.clone());
+                    }
+                    for sym_def in &dyn_data.provided_dynamic_symbols {
+                        definers
+                            .entry(sym_def.symbol.clone())
+                            .or_default()
+                            .push(bin.filename.clone());
+                    }
+                    let bin_resolutions = resolve_symbols(&dyn_data.dynamic_symbol_refs, &all_libs);
+                    let mut bin_unresolved = diagnose_unresolved(
+                        &dyn_data.dynamic_symbol_refs,
+                        &all_libs,
+                        &bin_resolutions,
+                    );
+                    resolved_syms.extend(
+                        bin_resolutions
+                            .into_iter()
+                            .map(|(sym, provider)| (sym, provider.filename.clone())),
+                    );
+                    unresolved_syms.append(&mut bin_unresolved);
+                }
+            }
+        }
+
+        let modification_times = std::iter::once(&elf_summary)
+            .chain(all_libs.iter().copied())
+            .map(|bin| (bin.filename.clone(), bin.modified))
+            .collect();
+
+        StaticAppData {
+            title: title.to_string(),
+            elf: elf_summary,
+            resolution,
+            modification_times,
+            symbol_resolutions: resolved_syms,
+            unresolved_symbols: unresolved_syms,
+            symbol_columns: table_options.columns.clone(),
+            human_sizes: table_options.human_sizes,
+            demangle_style: table_options.demangle_style,
+            // Toggling the filter on without any types requested shows the functions
+            export_types: if table_options.export_types.is_empty() {
+                vec![summarize::SymbolType::Func]
+            } else {
+                table_options.export_types.clone()
+            },
+            theme,
+            symbol_referrers: referrers,
+            symbol_definers: definers,
+        }
+    }
+
+    /// The binary in the closure (including the root binary) with the given path
+    pub fn binary(&self, path: &Path) -> Option<&summarize::ElfSummary> {
+        std::iter::once(&self.elf)
+            .chain(
+                self.resolution
+                    .dependencies
+                    .values()
+                    .filter_map(|d| d.as_ref()),
+            )
+            .find(|bin| bin.filename == path)
+    }
+
+    /// The binary selected in the sidebar, if any
+    ///
+    /// The result only borrows the binaries (not the UI `state`), so the UI
+    /// state of the selected binary can be updated while it is in use
+    pub fn selected_binary(&self, state: &MutableAppData) -> Option<&summarize::ElfSummary> {
+        match state.selected_binary.selected() {
+            None => None,
+            Some(idx) => {
+                // The root binary is the first item in the sidebar (its
+                // separator is part of the same item), followed by the
+                // dependencies in order
+                if idx == 0 {
+                    Some(&self.elf)
+                } else {
+                    let v = self.sidebar_dependencies(state);
+                    v.get(idx - 1).and_then(|entry| entry.dependency.as_ref())
+                }
+            }
+        }
+    }
+
+    /// The name that the selected dependency was requested by (i.e., its
+    /// `DT_NEEDED` entry), or `None` if the root binary (or nothing) is selected
+    pub fn selected_dependency_name(&self, state: &MutableAppData) -> Option<&str> {
+        match state.selected_binary.selected() {
+            None | Some(0) => None,
+            Some(idx) => self
+                .sidebar_dependencies(state)
+                .get(idx - 1)
+                .map(|entry| entry.name.as_str()),
+        }
+    }
+
+    /// The dependencies listed in the sidebar (after the root binary), in order
+    ///
+    /// These are the dependencies in the closure as a tree (leaving out the
+    /// dependencies of collapsed libraries), or a flat list of only those that
+    /// could not be found if `only_unresolved` is set
+    pub fn sidebar_dependencies(&self, state: &MutableAppData) -> Vec<SidebarEntry<'_>> {
+        if state.only_unresolved {
+            return self
+                .resolution
+                .dependencies
+                .iter()
+                .filter(|(_, dep)| dep.is_none())
+                .map(|(name, dependency)| SidebarEntry {
+                    name,
+                    dependency,
+                    depth: 0,
+                    expansion: Expansion::Leaf,
+                })
+                .collect();
+        }
+        let mut entries = Vec::new();
+        let mut seen = collections::HashSet::new();
+        let collapsed = &state.collapsed_dependencies;
+        add_tree_entries(self, collapsed, &self.elf, 0, &mut seen, &mut entries);
+        entries
+    }
+
+    /// The binaries in the closure whose references to `def` resolve to its definition in `provider`
+    pub fn referrers(
+        &self,
+        provider: &summarize::ElfSummary,
+        def: &summarize::VersionedSymbol,
+    ) -> Vec<&Path> {
+        // References are ordered by name (then version), so all of the
+        // references that could be satisfied by `def` are contiguous
+        let first = summarize::VersionedSymbol {
+            name: def.name.clone(),
+            version: None,
+        };
+        let mut res: Vec<&Path> = Vec::new();
+        for (sym_ref, bins) in self.symbol_referrers.range(first..) {
+            if sym_ref.name != def.name {
+                break;
+            }
+            if !satisfies(def, sym_ref) {
+                continue;
+            }
+            match self.symbol_resolutions.get(sym_ref) {
+                Some(p) if *p == provider.filename => res.extend(bins.iter().map(PathBuf::as_path)),
+                _ => {}
+            }
+        }
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// The dynamic symbol references of `elf_summ` to show in its table
+    ///
+    /// If `only_unresolved` is set, references that resolved are left out
+    pub fn symbol_references<'b>(
+        &self,
+        elf_summ: &'b summarize::ElfSummary,
+        only_unresolved: bool,
+    ) -> Vec<&'b summarize::DynamicSymbolReference> {
+        match &elf_summ.binary_type {
+            summarize::BinaryType::Static => Vec::new(),
+            summarize::BinaryType::Dynamic(dyn_data) => dyn_data
+                .dynamic_symbol_refs
+                .iter()
+                .filter(|r| !only_unresolved || !self.symbol_resolutions.contains_key(&r.symbol))
+                .collect(),
+        }
+    }
+
+    /// The defined dynamic symbols of `elf_summ` to show in its table
+    ///
+    /// If `filter_exports` is set, only the symbols of the `export_types` are included
+    pub fn defined_symbols<'b>(
+        &self,
+        elf_summ: &'b summarize::ElfSummary,
+        filter_exports: bool,
+    ) -> Vec<&'b summarize::ExportedDynamicSymbol> {
+        match &elf_summ.binary_type {
+            summarize::BinaryType::Static => Vec::new(),
+            summarize::BinaryType::Dynamic(dyn_data) => dyn_data
+                .provided_dynamic_symbols
+                .iter()
+                .filter(|d| !filter_exports || self.export_types.contains(&d.type_))
+                .collect(),
+        }
+    }
+
+    /// The symbol selected in the table of the selected binary, if a symbol
+    /// table is shown and has a selection
+    pub fn selected_symbol(&self, state: &MutableAppData) -> Option<SelectedSymbol<'_>> {
+        let elf_summ = self.selected_binary(state)?;
+        let ui_state = state.binary_ui_state.get(&elf_summ.filename)?;
+        match ui_state.tab_state.selected_label() {
+            InfoTabLabels::DynamicDependencies => {
+                let idx = ui_state.dynamic_reference_table_state.selected()?;
+                self.symbol_references(elf_summ, state.only_unresolved)
+                    .get(idx)
+                    .copied()
+                    .map(SelectedSymbol::Reference)
+            }
+            InfoTabLabels::DefinedDynamicSymbols => {
+                let idx = ui_state.defined_dynamic_table_state.selected()?;
+                self.defined_symbols(elf_summ, state.filter_exports)
+                    .get(idx)
+                    .map(|sym_def| SelectedSymbol::Definition(elf_summ, sym_def))
+            }
+            InfoTabLabels::Overview | InfoTabLabels::InitFini | InfoTabLabels::Sections => None,
+        }
+    }
+}
+
+/// A row of one of the symbol tables
+pub enum SelectedSymbol<'a> {
+    Reference(&'a summarize::DynamicSymbolReference),
+    /// A symbol defined by the given binary
+    Definition(
+        &'a summarize::ElfSummary,
+        &'a summarize::ExportedDynamicSymbol,
+    ),
+}
+
+/// Whether the dependencies of a library in the sidebar are shown
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Expansion {
+    /// The library has no dependencies to show
+    Leaf,
+    Expanded,
+    Collapsed,
+    /// The library appears earlier in the tree, where its dependencies are shown
+    Repeated,
+}
+
+/// A dependency listed in the sidebar
+pub struct SidebarEntry<'a> {
+    /// The name the library was requested by (i.e., its `DT_NEEDED` entry)
+    pub name: &'a String,
+    pub dependency: &'a Option<summarize::ElfSummary>,
+    /// The nesting depth in the tree (zero for the direct dependencies of the root binary)
+    pub depth: usize,
+    pub expansion: Expansion,
+}
+
+/// The dependencies of `bin` that are in the closure (i.e., not excluded or ignored)
+fn tree_children<'a>(
+    data: &'a StaticAppData,
+    bin: &'a summarize::ElfSummary,
+) -> Vec<(&'a String, &'a Option<summarize::ElfSummary>)> {
+    match &bin.binary_type {
+        summarize::BinaryType::Static => Vec::new(),
+        summarize::BinaryType::Dynamic(dyn_data) => dyn_data
+            .deps
+            .iter()
+            .filter_map(|name| data.resolution.dependencies.get_key_value(name))
+            .collect(),
+    }
+}
+
+/// Add the dependencies of `bin` to the sidebar tree, recursively
+///
+/// Each library is only expanded the first time it appears, so shared
+/// subtrees are not repeated (and cycles terminate)
+fn add_tree_entries<'a>(
+    data: &'a StaticAppData,
+    collapsed: &collections::BTreeSet<String>,
+    bin: &'a summarize::ElfSummary,
+    depth: usize,
+    seen: &mut collections::HashSet<&'a str>,
+    entries: &mut Vec<SidebarEntry<'a>>,
+) {
+    for (name, dependency) in tree_children(data, bin) {
+        let child = dependency
+            .as_ref()
+            .filter(|&dep| !tree_children(data, dep).is_empty());
+        let expansion = match child {
+            None => Expansion::Leaf,
+            Some(_) if !seen.insert(name.as_str()) => Expansion::Repeated,
+            Some(_) if collapsed.contains(name) => Expansion::Collapsed,
+            Some(_) => Expansion::Expanded,
+        };
+        entries.push(SidebarEntry {
+            name,
+            dependency,
+            depth,
+            expansion,
+        });
+        if let (Some(dep), Expansion::Expanded) = (child, expansion) {
+            add_tree_entries(data, collapsed, dep, depth + 1, seen, entries);
+        }
+    }
 }
 
 pub struct MutableAppData {
     pub should_quit: bool,
+    /// Set when the binaries on disk have changed (or a reload was requested)
+    /// and should be re-summarized
+    pub should_reload: bool,
+    /// A message about the result of the last action (e.g., an export), if any
+    pub status_message: Option<String>,
+    /// Show only the dependencies that could not be found and the symbol
+    /// references that could not be resolved
+    pub only_unresolved: bool,
+    /// Show only the defined symbols of the types in `StaticAppData::export_types`
+    pub filter_exports: bool,
+    /// The address being typed for a symbol lookup, while one is in progress
+    pub address_input: Option<String>,
+    /// The command palette, while it is open
+    pub command_palette: Option<CommandPalette>,
+    /// Show the attributes of the selected symbol in a popup
+    pub show_symbol_details: bool,
+    /// The libraries whose dependencies are hidden in the sidebar
+    pub collapsed_dependencies: collections::BTreeSet<String>,
     pub selected_binary: ListState,
     /// The state of the tab widget for each binary
     ///
@@ -134,45 +634,36 @@ impl MutableAppData {
     }
 }
 
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
 /// Application state
-pub struct App<'a> {
-    pub static_app_data: StaticAppData<'a>,
+pub struct App {
+    pub static_app_data: StaticAppData,
     pub mutable_app_data: MutableAppData,
 }
 
-impl<'a> App<'a> {
+impl App {
     pub fn new(
         title: &str,
-        elf_summary: &'a summarize::ElfSummary,
-        resolved_deps: &'a collections::BTreeMap<String, Option<summarize::ElfSummary>>,
+        elf_summary: summarize::ElfSummary,
+        resolution: dependencies::Resolution,
+        table_options: &SymbolTableOptions,
+        theme: Theme,
     ) -> Self {
-        let all_libs = resolved_deps.values().filter_map(|x| x.as_ref()).collect();
-        let mut resolved_syms = match &elf_summary.binary_type {
-            summarize::BinaryType::Static => collections::BTreeMap::new(),
-            summarize::BinaryType::Dynamic(dyn_data) => {
-                resolve_symbols(&dyn_data.dynamic_symbol_refs, &all_libs)
-            }
-        };
-        for lib in &all_libs {
-            match &lib.binary_type {
-                summarize::BinaryType::Static => {}
-                summarize::BinaryType::Dynamic(dyn_data) => {
-                    let mut lib_resolutions =
-                        resolve_symbols(&dyn_data.dynamic_symbol_refs, &all_libs);
-                    resolved_syms.append(&mut lib_resolutions);
-                }
-            }
-        }
-
-        let static_data = StaticAppData {
-            title: title.to_string(),
-            elf: elf_summary,
-            resolved_dependencies: resolved_deps,
-            symbol_resolutions: resolved_syms,
-        };
+        let static_data = StaticAppData::new(title, elf_summary, resolution, table_options, theme);
 
         let mutable_data = MutableAppData {
             should_quit: false,
+            should_reload: false,
+            status_message: None,
+            only_unresolved: false,
+            filter_exports: !table_options.export_types.is_empty(),
+            address_input: None,
+            command_palette: None,
+            show_symbol_details: false,
+            collapsed_dependencies: collections::BTreeSet::new(),
             selected_binary: ListState::default(),
             binary_ui_state: collections::BTreeMap::new(),
         };
@@ -183,32 +674,380 @@ impl<'a> App<'a> {
         }
     }
 
-    pub fn selected_binary(&self) -> Option<&'a summarize::ElfSummary> {
-        match self.mutable_app_data.selected_binary.selected() {
-            None => None,
-            Some(idx) => {
-                if idx == 0 {
-                    Some(self.static_app_data.elf)
-                } else {
-                    let v = self
-                        .static_app_data
-                        .resolved_dependencies
-                        .values()
-                        .map(|o| o.as_ref())
-                        .collect::<Vec<Option<&summarize::ElfSummary>>>();
-                    v[idx - 1]
+    /// The binary selected in the sidebar (see [`StaticAppData::selected_binary`])
+    pub fn selected_binary(&self) -> Option<&summarize::ElfSummary> {
+        self.static_app_data.selected_binary(&self.mutable_app_data)
+    }
+
+    /// The dependencies listed in the sidebar (see [`StaticAppData::sidebar_dependencies`])
+    pub fn sidebar_dependencies(&self) -> Vec<SidebarEntry<'_>> {
+        self.static_app_data
+            .sidebar_dependencies(&self.mutable_app_data)
+    }
+
+    /// Open the detail popup for the selected symbol, returning false if no
+    /// symbol is selected
+    fn show_selected_symbol(&mut self) -> bool {
+        let selected = self
+            .static_app_data
+            .selected_symbol(&self.mutable_app_data)
+            .is_some();
+        self.mutable_app_data.show_symbol_details = selected;
+        selected
+    }
+
+    /// Show the selected symbol if a symbol table has a selection, and
+    /// otherwise collapse or expand the library selected in the sidebar
+    fn activate_selection(&mut self) {
+        if !self.show_selected_symbol() {
+            self.toggle_selected_subtree();
+        }
+    }
+
+    /// Collapse or expand the dependencies of the library selected in the sidebar
+    ///
+    /// Only the entries after the selection change, so it remains valid
+    fn toggle_selected_subtree(&mut self) {
+        let idx = match self.mutable_app_data.selected_binary.selected() {
+            None | Some(0) => return,
+            Some(idx) => idx,
+        };
+        let entries = self
+            .static_app_data
+            .sidebar_dependencies(&self.mutable_app_data);
+        let entry = match entries.get(idx - 1) {
+            None => return,
+            Some(entry) => entry,
+        };
+        let collapsed = &mut self.mutable_app_data.collapsed_dependencies;
+        match entry.expansion {
+            Expansion::Expanded => {
+                collapsed.insert(entry.name.clone());
+            }
+            Expansion::Collapsed => {
+                collapsed.remove(entry.name.as_str());
+            }
+            Expansion::Leaf | Expansion::Repeated => {}
+        }
+    }
+
+    /// Toggle between showing everything and showing only the unresolved
+    /// dependencies and symbol references
+    ///
+    /// The selections are adjusted to stay within the (possibly shorter) lists
+    fn toggle_only_unresolved(&mut self) {
+        let state = &mut self.mutable_app_data;
+        state.only_unresolved = !state.only_unresolved;
+        state.status_message = Some(String::from(if state.only_unresolved {
+            "Showing only unresolved dependencies and references"
+        } else {
+            "Showing all dependencies and references"
+        }));
+        for ui_state in state.binary_ui_state.values_mut() {
+            ui_state.dynamic_reference_table_state.select(None);
+        }
+        let num_bins = 1 + self.sidebar_dependencies().len();
+        if let Some(idx) = self.mutable_app_data.selected_binary.selected() {
+            self.mutable_app_data
+                .selected_binary
+                .select(Some(std::cmp::min(idx, num_bins - 1)));
+        }
+    }
+
+    /// Toggle between showing all of the defined symbols and only those of the `export_types`
+    fn toggle_filter_exports(&mut self) {
+        let state = &mut self.mutable_app_data;
+        state.filter_exports = !state.filter_exports;
+        state.status_message = Some(if state.filter_exports {
+            let types: Vec<String> = self
+                .static_app_data
+                .export_types
+                .iter()
+                .map(|t| format!("{:?}", t))
+                .collect();
+            format!("Showing only defined symbols of type {}", types.join(", "))
+        } else {
+            String::from("Showing all defined symbols")
+        });
+        for ui_state in state.binary_ui_state.values_mut() {
+            ui_state.defined_dynamic_table_state.select(None);
+        }
+    }
+
+    /// Look up the address typed by the user in the selected binary
+    ///
+    /// This reports the defined symbol and section containing the address (if
+    /// any), and selects the symbol in the defined symbols table
+    fn look_up_address(&mut self, input: &str) {
+        let digits = input.trim_start_matches("0x").trim_start_matches("0X");
+        let addr = match u64::from_str_radix(digits, 16) {
+            Ok(addr) => addr,
+            Err(_) => {
+                self.mutable_app_data.status_message = Some(format!("Invalid address: {}", input));
+                return;
+            }
+        };
+        let elf_summ = match self.static_app_data.selected_binary(&self.mutable_app_data) {
+            None => {
+                self.mutable_app_data.status_message =
+                    Some(String::from("Select a binary to look up an address in"));
+                return;
+            }
+            Some(elf_summ) => elf_summ,
+        };
+
+        let section = elf_summ
+            .section_containing(addr)
+            .map_or(String::from("not in any section"), |s| {
+                format!("in section {}", s.name)
+            });
+        let filter_exports = self.mutable_app_data.filter_exports;
+        let defined = self
+            .static_app_data
+            .defined_symbols(elf_summ, filter_exports);
+        let found = defined
+            .iter()
+            .enumerate()
+            .find(|(_, sym_def)| sym_def.contains_address(addr));
+        let message = match found {
+            None => format!(
+                "{} is not in any defined symbol ({})",
+                elf_summ.format_address(addr),
+                section
+            ),
+            Some((idx, sym_def)) => {
+                let ui_state = self.mutable_app_data.binary_ui_state(elf_summ);
+                let tabs = &mut ui_state.tab_state;
+                if let Some(tab) = tabs
+                    .tab_labels
+                    .iter()
+                    .position(|l| matches!(l, InfoTabLabels::DefinedDynamicSymbols))
+                {
+                    tabs.selected_tab = tab;
+                }
+                ui_state.defined_dynamic_table_state.select(Some(idx));
+                format!(
+                    "{} is {}+{:#x} ({})",
+                    elf_summ.format_address(addr),
+                    sym_def.symbol,
+                    addr - sym_def.address,
+                    section
+                )
+            }
+        };
+        self.mutable_app_data.status_message = Some(message);
+    }
+
+    /// Handle a key while an address is being typed for a lookup
+    fn on_address_key(&mut self, evt: KeyEvent) {
+        match evt.code {
+            KeyCode::Esc => {
+                self.mutable_app_data.address_input = None;
+            }
+            KeyCode::Enter => {
+                if let Some(input) = self.mutable_app_data.address_input.take() {
+                    self.look_up_address(&input);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(input) = &mut self.mutable_app_data.address_input {
+                    input.pop();
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_hexdigit() || c == 'x' || c == 'X' => {
+                if let Some(input) = &mut self.mutable_app_data.address_input {
+                    input.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn run_command(&mut self, command: Command) {
+        match command {
+            Command::ToggleFilterExports => self.toggle_filter_exports(),
+            Command::ToggleOnlyUnresolved => self.toggle_only_unresolved(),
+            Command::ToggleSubtree => self.toggle_selected_subtree(),
+            Command::LookUpAddress => {
+                self.mutable_app_data.address_input = Some(String::new());
+            }
+            Command::Export => self.export_selected(),
+            Command::Reload => {
+                self.mutable_app_data.should_reload = true;
+            }
+            Command::Quit => {
+                self.mutable_app_data.should_quit = true;
+            }
+        }
+    }
+
+    fn on_palette_key(&mut self, evt: KeyEvent) {
+        let palette = match &mut self.mutable_app_data.command_palette {
+            None => return,
+            Some(palette) => palette,
+        };
+        match evt.code {
+            KeyCode::Esc => {
+                self.mutable_app_data.command_palette = None;
+            }
+            KeyCode::Enter => {
+                let selected = palette.matches().get(palette.selected).copied();
+                self.mutable_app_data.command_palette = None;
+                if let Some(command) = selected {
+                    self.run_command(command);
                 }
             }
+            KeyCode::Up => {
+                palette.selected = palette.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                let num_matches = palette.matches().len();
+                if palette.selected + 1 < num_matches {
+                    palette.selected += 1;
+                }
+            }
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Char(c) if !evt.modifiers.contains(KeyModifiers::CONTROL) => {
+                palette.query.push(c);
+                palette.selected = 0;
+            }
+            _ => {}
         }
     }
 
+    /// Replace the binaries (e.g., after reloading them from disk), keeping the
+    /// UI state (such as the selected tab of each binary)
+    ///
+    /// The binary selection is clamped, as the number of binaries may have changed
+    pub fn reload(
+        &mut self,
+        elf_summary: summarize::ElfSummary,
+        resolution: dependencies::Resolution,
+    ) {
+        let data = &self.static_app_data;
+        let title = data.title.clone();
+        let theme = data.theme;
+        let table_options = SymbolTableOptions {
+            columns: data.symbol_columns.clone(),
+            human_sizes: data.human_sizes,
+            demangle_style: data.demangle_style,
+            export_types: data.export_types.clone(),
+        };
+        self.static_app_data =
+            StaticAppData::new(&title, elf_summary, resolution, &table_options, theme);
+        self.mutable_app_data.should_reload = false;
+        self.mutable_app_data.show_symbol_details = false;
+        let num_bins = 1 + self.sidebar_dependencies().len();
+        if let Some(idx) = self.mutable_app_data.selected_binary.selected() {
+            self.mutable_app_data
+                .selected_binary
+                .select(Some(std::cmp::min(idx, num_bins - 1)));
+        }
+    }
+
+    /// Check if any of the binaries have been modified on disk, requesting a reload if so
+    pub fn on_tick(&mut self) {
+        let modified = self
+            .static_app_data
+            .modification_times
+            .iter()
+            .any(|(path, mtime)| modification_time(path) != *mtime);
+        if modified {
+            self.mutable_app_data.should_reload = true;
+        }
+    }
+
+    /// Write the JSON report for the selected binary to the current directory
+    ///
+    /// This uses the same serialization as `--format json`; the result is
+    /// reported through the status message
+    fn export_selected(&mut self) {
+        let bin = match self.selected_binary() {
+            None => {
+                self.mutable_app_data.status_message =
+                    Some(String::from("Select a binary to export"));
+                return;
+            }
+            Some(bin) => bin,
+        };
+
+        let data = &self.static_app_data;
+        let resolutions: collections::BTreeMap<_, _> = data
+            .symbol_resolutions
+            .iter()
+            .filter_map(|(sym, path)| data.binary(path).map(|b| (sym.clone(), b)))
+            .collect();
+        let report = json::binary_report(
+            bin,
+            &data.resolution,
+            &resolutions,
+            &data.unresolved_symbols,
+        );
+        let base_name = bin
+            .filename
+            .file_name()
+            .map_or(String::from("binary"), |n| n.to_string_lossy().into_owned());
+        let export_path = PathBuf::from(format!("{}.json", base_name));
+        let res = serde_json::to_string_pretty(&report)
+            .map_err(anyhow::Error::from)
+            .and_then(|s| fs::write(&export_path, s).map_err(anyhow::Error::from));
+        self.mutable_app_data.status_message = Some(match res {
+            Ok(()) => format!("Exported to {}", export_path.to_string_lossy()),
+            Err(err) => format!("Export failed: {}", err),
+        });
+    }
+
     pub fn on_key(&mut self, evt: KeyEvent) {
+        if self.mutable_app_data.address_input.is_some() {
+            self.on_address_key(evt);
+            return;
+        }
+        if self.mutable_app_data.command_palette.is_some() {
+            self.on_palette_key(evt);
+            return;
+        }
+        if self.mutable_app_data.show_symbol_details {
+            if evt.code == KeyCode::Esc {
+                self.mutable_app_data.show_symbol_details = false;
+            }
+            return;
+        }
         match evt.code {
+            KeyCode::Char(':') => {
+                self.mutable_app_data.address_input = Some(String::new());
+            }
+            KeyCode::Char('k') if evt.modifiers == KeyModifiers::CONTROL => {
+                self.mutable_app_data.command_palette = Some(CommandPalette::default());
+            }
+            KeyCode::Char('e') if evt.modifiers == KeyModifiers::NONE => {
+                self.export_selected();
+            }
+            KeyCode::Char('r') if evt.modifiers == KeyModifiers::NONE => {
+                self.mutable_app_data.should_reload = true;
+            }
+            KeyCode::Char('u') if evt.modifiers == KeyModifiers::NONE => {
+                self.toggle_only_unresolved();
+            }
+            KeyCode::Char('t') if evt.modifiers == KeyModifiers::NONE => {
+                self.toggle_filter_exports();
+            }
+            KeyCode::Char('i') if evt.modifiers == KeyModifiers::NONE => {
+                self.show_selected_symbol();
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_selected_subtree();
+            }
+            KeyCode::Enter => {
+                self.activate_selection();
+            }
             KeyCode::Char('q') if evt.modifiers == KeyModifiers::CONTROL => {
                 self.mutable_app_data.should_quit = true;
             }
             KeyCode::Char('p') if evt.modifiers == KeyModifiers::CONTROL => {
-                let num_bins = 1 + self.static_app_data.resolved_dependencies.len();
+                let num_bins = 1 + self.sidebar_dependencies().len();
                 match self.mutable_app_data.selected_binary.selected() {
                     None => {
                         self.mutable_app_data
@@ -226,7 +1065,7 @@ impl<'a> App<'a> {
                 }
             }
             KeyCode::Char('n') if evt.modifiers == KeyModifiers::CONTROL => {
-                let num_bins = 1 + self.static_app_data.resolved_dependencies.len();
+                let num_bins = 1 + self.sidebar_dependencies().len();
                 match self.mutable_app_data.selected_binary.selected() {
                     None => {
                         self.mutable_app_data.selected_binary.select(Some(0));
@@ -245,7 +1084,7 @@ impl<'a> App<'a> {
             {
                 // c.is_ascii_digit()
                 // The user wants to switch info pane using ALT+#
-                match self.selected_binary() {
+                match self.static_app_data.selected_binary(&self.mutable_app_data) {
                     None => {}
                     Some(bin) => {
                         // If we are here at all, the tab state has been instantiated
@@ -263,64 +1102,34 @@ impl<'a> App<'a> {
                     }
                 }
             }
-            KeyCode::Up => {
-                match self.selected_binary() {
-                    None => {}
-                    Some(elf_summ) => match &elf_summ.binary_type {
-                        summarize::BinaryType::Static => {}
-                        summarize::BinaryType::Dynamic(dyn_data) => {
-                            let ui_state = self.mutable_app_data.binary_ui_state(elf_summ);
-                            let selected_tab = ui_state.tab_state.selected_label();
-                            match selected_tab {
-                                InfoTabLabels::DefinedDynamicSymbols => {
-                                    let num_items = dyn_data.provided_dynamic_symbols.len();
-                                    decrement_table_selection(
-                                        &mut ui_state.defined_dynamic_table_state,
-                                        num_items,
-                                    );
-                                }
-                                InfoTabLabels::DynamicDependencies => {
-                                    let num_items = dyn_data.dynamic_symbol_refs.len();
-                                    decrement_table_selection(
-                                        &mut ui_state.dynamic_reference_table_state,
-                                        num_items,
-                                    );
-                                }
-                                InfoTabLabels::Overview => {}
-                            }
-                        }
-                    },
+            KeyCode::Up => match self.static_app_data.selected_binary(&self.mutable_app_data) {
+                None => {}
+                Some(elf_summ) => {
+                    let only_unresolved = self.mutable_app_data.only_unresolved;
+                    let filter_exports = self.mutable_app_data.filter_exports;
+                    let ui_state = self.mutable_app_data.binary_ui_state(elf_summ);
+                    let data = &self.static_app_data;
+                    if let Some((table_state, num_items)) =
+                        selected_table(ui_state, data, elf_summ, only_unresolved, filter_exports)
+                    {
+                        decrement_table_selection(table_state, num_items);
+                    }
                 }
-            }
-            KeyCode::Down => {
-                match self.selected_binary() {
-                    None => {}
-                    Some(elf_summ) => match &elf_summ.binary_type {
-                        summarize::BinaryType::Static => {}
-                        summarize::BinaryType::Dynamic(dyn_data) => {
-                            let ui_state = self.mutable_app_data.binary_ui_state(elf_summ);
-                            let selected_tab = ui_state.tab_state.selected_label();
-                            match selected_tab {
-                                InfoTabLabels::Overview => {}
-                                InfoTabLabels::DefinedDynamicSymbols => {
-                                    let num_items = dyn_data.provided_dynamic_symbols.len();
-                                    increment_table_selection(
-                                        &mut ui_state.defined_dynamic_table_state,
-                                        num_items,
-                                    );
-                                }
-                                InfoTabLabels::DynamicDependencies => {
-                                    let num_items = dyn_data.dynamic_symbol_refs.len();
-                                    increment_table_selection(
-                                        &mut ui_state.dynamic_reference_table_state,
-                                        num_items,
-                                    );
-                                }
-                            }
-                        }
-                    },
+            },
+            KeyCode::Down => match self.static_app_data.selected_binary(&self.mutable_app_data) {
+                None => {}
+                Some(elf_summ) => {
+                    let only_unresolved = self.mutable_app_data.only_unresolved;
+                    let filter_exports = self.mutable_app_data.filter_exports;
+                    let ui_state = self.mutable_app_data.binary_ui_state(elf_summ);
+                    let data = &self.static_app_data;
+                    if let Some((table_state, num_items)) =
+                        selected_table(ui_state, data, elf_summ, only_unresolved, filter_exports)
+                    {
+                        increment_table_selection(table_state, num_items);
+                    }
                 }
-            }
+            },
             _ => {}
         }
     }
@@ -1,27 +1,39 @@
 use crossterm;
 use crossterm::{
+    cursor,
     event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    tty::IsTty,
 };
 use std::{
-    collections, io,
+    io,
     time::{Duration, Instant},
 };
 use tui;
 
+use crate::dependencies;
 use crate::summarize;
 use crate::ui::app;
 use crate::ui::draw;
+use crate::ui::theme::Theme;
+
+/// The reasons that the event loop can exit
+enum LoopExit {
+    Quit,
+    /// The binaries changed on disk (or a reload was requested)
+    Reload,
+}
 
 fn run_app<B: tui::backend::Backend>(
     terminal: &mut tui::Terminal<B>,
-    mut app: app::App,
+    app: &mut app::App,
     tick_rate: Duration,
-) -> anyhow::Result<()> {
+    watch: bool,
+) -> anyhow::Result<LoopExit> {
     let mut last_tick = Instant::now();
     loop {
-        terminal.draw(|f| draw::draw(f, &mut app))?;
+        terminal.draw(|f| draw::draw(f, app))?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
@@ -32,43 +44,99 @@ fn run_app<B: tui::backend::Backend>(
             }
         }
         if last_tick.elapsed() >= tick_rate {
-            // app.on_tick();
+            if watch {
+                app.on_tick();
+            }
             last_tick = Instant::now();
         }
         if app.mutable_app_data.should_quit {
-            return Ok(());
+            return Ok(LoopExit::Quit);
+        }
+        if app.mutable_app_data.should_reload {
+            return Ok(LoopExit::Reload);
+        }
+    }
+}
+
+/// The inputs to the UI: the root binary and its resolved dependencies
+pub type LoadedBinaries = (summarize::ElfSummary, dependencies::Resolution);
+
+/// Restores the terminal to its normal state when dropped
+struct TerminalGuard {
+    mouse_capture: bool,
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // There is no way to report errors from here, and restoring as much as
+        // possible is better than stopping at the first failure
+        let _ = disable_raw_mode();
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, LeaveAlternateScreen);
+        if self.mouse_capture {
+            let _ = execute!(stdout, DisableMouseCapture);
         }
+        let _ = execute!(stdout, cursor::Show);
     }
 }
 
-pub fn run(
+/// Run the UI for the binaries produced by `load`
+///
+/// The binaries are re-loaded (via `load`) when requested with `r` and, if
+/// `watch` is set, whenever they change on disk; if re-loading fails (e.g.,
+/// because the binary is being rebuilt), the previous state is kept and the
+/// error is shown in the status message.
+///
+/// Mouse capture interferes with selecting text in some terminals, so it can be
+/// disabled with `mouse_capture`.  The symbol tables are shown as described by
+/// the `table_options`, and everything is drawn with the colors of the `theme`.
+pub fn run<F>(
     tick_rate: Duration,
-    elf: &summarize::ElfSummary,
-    resolved_deps: &collections::BTreeMap<String, Option<summarize::ElfSummary>>,
-) -> anyhow::Result<()> {
-    // setup terminal
+    watch: bool,
+    mouse_capture: bool,
+    table_options: &app::SymbolTableOptions,
+    theme: Theme,
+    load: F,
+) -> anyhow::Result<()>
+where
+    F: Fn() -> anyhow::Result<LoadedBinaries>,
+{
+    if !io::stdout().is_tty() {
+        anyhow::bail!("The interactive UI requires stdout to be a terminal");
+    }
+
+    let (elf, resolution) = load()?;
+
+    // setup terminal; the guard restores it on every exit path (including
+    // errors and panics) once raw mode has been entered
     enable_raw_mode()?;
+    let _guard = TerminalGuard { mouse_capture };
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse_capture {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = tui::backend::CrosstermBackend::new(stdout);
     let mut terminal = tui::Terminal::new(backend)?;
 
-    // create app and run it
-    let app = app::App::new("binary-walkr", elf, resolved_deps);
-    let res = run_app(&mut terminal, app, tick_rate);
-
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-
-    if let Err(err) = res {
-        println!("{:?}", err)
+    // create app and run it, replacing its binaries each time they are reloaded
+    let mut app = app::App::new("binary-walkr", elf, resolution, table_options, theme);
+    loop {
+        match run_app(&mut terminal, &mut app, tick_rate, watch)? {
+            LoopExit::Quit => return Ok(()),
+            LoopExit::Reload => {
+                let status = match load() {
+                    Ok((new_elf, new_resolution)) => {
+                        app.reload(new_elf, new_resolution);
+                        String::from("Reloaded from disk")
+                    }
+                    Err(err) => {
+                        app.mutable_app_data.should_reload = false;
+                        format!("Reload failed: {}", err)
+                    }
+                };
+                app.mutable_app_data.status_message = Some(status);
+            }
+        }
     }
-
-    Ok(())
 }
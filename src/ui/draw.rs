@@ -1,32 +1,87 @@
-use crate::summarize::{BinaryType, ElfSummary, VersionedSymbol};
-use crate::ui::app::{App, BinaryUIState, InfoTabLabels};
+use crate::columns;
+use crate::demangle;
+use crate::dependencies::IncompatibleCandidate;
+use crate::options::SymbolColumn;
+use crate::summarize::{BinaryType, ElfSummary, SymbolBinding, VersionedSymbol};
+use crate::ui::app::{
+    App, BinaryUIState, CommandPalette, Expansion, InfoTabLabels, SelectedSymbol, StaticAppData,
+};
+use crate::ui::theme::Theme;
 
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table, Tabs},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table, Tabs, Wrap,
+    },
     Frame,
 };
 
 fn draw_binary_list_sidebar<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
-    let mut items = vec![ListItem::new(Text::from(
+    let theme = app.static_app_data.theme;
+    // The root binary is pinned at the top in bold, with a separator before
+    // its dependencies.  The separator is part of the same list item so that
+    // the indices of the items still match `App::selected_binary`
+    let mut root_lines = vec![Spans::from(Span::styled(
         app.static_app_data.elf.filename.as_path().to_string_lossy(),
+        Style::default().add_modifier(Modifier::BOLD),
     ))];
+    let deps = app.sidebar_dependencies();
+    if !deps.is_empty() {
+        // Leave room for the borders and the highlight symbol
+        let width = area.width.saturating_sub(4) as usize;
+        root_lines.push(Spans::from(Span::styled("─".repeat(width), theme.muted)));
+    }
+    let mut items = vec![ListItem::new(Text::from(root_lines))];
 
-    for lib in app.static_app_data.resolved_dependencies.keys() {
-        items.push(ListItem::new(Text::from(format!("  {}", lib))));
+    for entry in deps {
+        // Mark each dependency with its resolution status so that problems are
+        // visible without selecting each entry
+        let (marker, style, suffix) = match entry.dependency {
+            None => ("✗", theme.unresolved, ""),
+            Some(dep) => match dep.binary_type {
+                BinaryType::Static => ("✓", theme.resolved, " (static)"),
+                BinaryType::Dynamic(_) => ("✓", theme.resolved, ""),
+            },
+        };
+        // Show where the library was found (e.g., via RPATH) to explain why
+        // a particular copy was chosen
+        let mut source = app
+            .static_app_data
+            .resolution
+            .sources
+            .get(entry.name)
+            .map_or(String::new(), |s| format!(" [{}]", s));
+        let toggle = match entry.expansion {
+            Expansion::Expanded => "▾ ",
+            Expansion::Collapsed => "▸ ",
+            Expansion::Leaf => "  ",
+            Expansion::Repeated => {
+                source.push_str(" (dependencies shown above)");
+                "  "
+            }
+        };
+        items.push(ListItem::new(Spans::from(vec![
+            Span::raw("  ".repeat(entry.depth + 1)),
+            Span::styled(toggle, theme.muted),
+            Span::styled(marker, style),
+            Span::raw(format!(" {}{}", entry.name, suffix)),
+            Span::styled(source, theme.muted),
+        ])));
     }
 
+    let title = if app.mutable_app_data.only_unresolved {
+        "Binary Images (unresolved only)"
+    } else {
+        "Binary Images"
+    };
     let w = List::new(items)
-        .block(
-            Block::default()
-                .title("Binary Images")
-                .borders(Borders::ALL),
-        )
+        .block(Block::default().title(title).borders(Borders::ALL))
         .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
         .highlight_symbol(">>");
     f.render_stateful_widget(w, area, &mut app.mutable_app_data.selected_binary);
@@ -39,18 +94,131 @@ fn draw_endian(end: object::Endianness) -> &'static str {
     }
 }
 
-fn draw_binary_overview<B: Backend>(f: &mut Frame<B>, elf_summ: &ElfSummary, area: Rect) {
-    let overview_data = vec![
-        Row::new(vec![
-            String::from("Path:"),
-            elf_summ.filename.as_path().to_string_lossy().into_owned(),
-        ]),
+/// Draw the overview of a binary
+///
+/// If the binary is a dependency (rather than the root binary), `requested_name`
+/// is the name that it was requested by; the `incompatible` libraries skipped
+/// while resolving dependencies are reported as warnings
+fn draw_binary_overview<B: Backend>(
+    f: &mut Frame<B>,
+    elf_summ: &ElfSummary,
+    requested_name: Option<&str>,
+    incompatible: &[IncompatibleCandidate],
+    theme: &Theme,
+    area: Rect,
+) {
+    let mut overview_data = vec![Row::new(vec![
+        String::from("Path:"),
+        elf_summ.filename.as_path().to_string_lossy().into_owned(),
+    ])];
+    if let Some(name) = requested_name {
+        // The path found by the search may be a symbolic link, so show the file
+        // that it actually resolves to
+        let real_path = fs::canonicalize(elf_summ.filename.as_path())
+            .map_or(String::from("<Unavailable>"), |p| {
+                p.as_path().to_string_lossy().into_owned()
+            });
+        overview_data.push(Row::new(vec![
+            String::from("Requested As:"),
+            name.to_string(),
+        ]));
+        overview_data.push(Row::new(vec![String::from("Real Path:"), real_path]));
+    }
+    overview_data.extend(vec![
         Row::new(vec!["Endianness:", draw_endian(elf_summ.endianness)]),
         Row::new(vec![
             String::from("Pointer Width: "),
             format!("{} bits", elf_summ.bit_size),
         ]),
-    ];
+        Row::new(vec![
+            String::from("Hash Tables:"),
+            columns::format_hash_tables(elf_summ),
+        ]),
+        Row::new(vec![String::from("SHA-256:"), elf_summ.sha256.clone()]),
+    ]);
+    let footprint = elf_summ.load_footprint();
+    overview_data.push(Row::new(vec![
+        String::from("Loadable Segments:"),
+        footprint.segments.to_string(),
+    ]));
+    overview_data.push(Row::new(vec![
+        String::from("Mapped Memory:"),
+        format!(
+            "{} ({} executable, {} writable, {} read-only)",
+            columns::format_size(footprint.total, true),
+            columns::format_size(footprint.executable, true),
+            columns::format_size(footprint.writable, true),
+            columns::format_size(footprint.read_only, true)
+        ),
+    ]));
+    if let BinaryType::Dynamic(dyn_data) = &elf_summ.binary_type {
+        overview_data.push(Row::new(vec![
+            String::from("Exports:"),
+            format!(
+                "{} functions, {} objects",
+                dyn_data.exported_functions().count(),
+                dyn_data.exported_objects().count()
+            ),
+        ]));
+        overview_data.push(Row::new(vec![
+            String::from("Relocations:"),
+            format!(
+                "{} PLT, {} dynamic",
+                dyn_data.relocation_counts.plt, dyn_data.relocation_counts.dynamic
+            ),
+        ]));
+        if let Some(glibc) = dyn_data.minimum_glibc_version() {
+            overview_data.push(Row::new(vec![String::from("Minimum glibc:"), glibc]));
+        }
+        let flags = dyn_data.flag_names();
+        if !flags.is_empty() {
+            overview_data.push(Row::new(vec![
+                String::from("Dynamic Flags:"),
+                flags.join(" "),
+            ]));
+        }
+        for (tag, libs) in dyn_data.filters_and_auditors() {
+            overview_data.push(Row::new(vec![format!("{}:", tag), libs.join(", ")]));
+        }
+        let incompatible_warnings = incompatible.iter().map(|c| c.to_string());
+        for warning in dyn_data
+            .hash_table_warnings()
+            .into_iter()
+            .chain(incompatible_warnings)
+        {
+            overview_data
+                .push(Row::new(vec![String::from("Warning:"), warning]).style(theme.warning));
+        }
+    }
+    for warning in elf_summ.segment_warnings() {
+        overview_data.push(Row::new(vec![String::from("Warning:"), warning]).style(theme.warning));
+    }
+    if let Some(core) = &elf_summ.core {
+        overview_data.push(Row::new(vec![
+            String::from("Core Dump Of:"),
+            core.executable
+                .clone()
+                .unwrap_or_else(|| String::from("<Unknown>")),
+        ]));
+        if let Some(args) = &core.arguments {
+            overview_data.push(Row::new(vec![String::from("Command Line:"), args.clone()]));
+        }
+        if let Some(signal) = core.signal {
+            let signal_str = match core.signal_name() {
+                None => signal.to_string(),
+                Some(name) => format!("{} ({})", signal, name),
+            };
+            overview_data.push(Row::new(vec![String::from("Signal:"), signal_str]));
+        }
+        for (idx, lib) in core.shared_libraries().into_iter().enumerate() {
+            let label = if idx == 0 { "Mapped Libraries:" } else { "" };
+            overview_data.push(Row::new(vec![label.to_string(), lib.to_string()]));
+        }
+    }
+    for (idx, comment) in elf_summ.comment.iter().enumerate() {
+        let label = if idx == 0 { "Comment:" } else { "" };
+        overview_data.push(Row::new(vec![label.to_string(), comment.clone()]));
+    }
     let overview = Table::new(overview_data)
         .column_spacing(1)
         .widths(&[Constraint::Min(15), Constraint::Ratio(5, 6)])
@@ -58,45 +226,83 @@ fn draw_binary_overview<B: Backend>(f: &mut Frame<B>, elf_summ: &ElfSummary, are
     f.render_widget(overview, area);
 }
 
+fn column_width(col: SymbolColumn) -> Constraint {
+    match col {
+        SymbolColumn::Addr => Constraint::Min(18),
+        SymbolColumn::Size => Constraint::Min(5),
+        SymbolColumn::Type | SymbolColumn::Binding => Constraint::Min(12),
+        SymbolColumn::Version => Constraint::Min(14),
+        SymbolColumn::Visibility => Constraint::Min(10),
+        SymbolColumn::Section => Constraint::Min(12),
+        SymbolColumn::Name | SymbolColumn::Demangled | SymbolColumn::Provider => {
+            Constraint::Length(40)
+        }
+    }
+}
+
+/// The style of a symbol table row, based on the binding of the symbol
+///
+/// Weak symbols are highlighted since they are easy to overlook, but can be
+/// left unresolved (or be overridden by another library)
+fn binding_style(theme: &Theme, binding: SymbolBinding) -> Style {
+    match binding {
+        SymbolBinding::Local => theme.local_symbol,
+        SymbolBinding::Weak => theme.weak_symbol,
+        SymbolBinding::Global | SymbolBinding::Unknown => Style::default(),
+    }
+}
+
+/// Draw the dynamic symbol references of the binary (only those that are
+/// unresolved if `only_unresolved` is set)
 fn draw_dynamic_dependencies<B: Backend>(
     f: &mut Frame<B>,
+    data: &StaticAppData,
     elf_summ: &ElfSummary,
-    resolutions: &BTreeMap<VersionedSymbol, &ElfSummary>,
+    only_unresolved: bool,
     ui_state: &mut BinaryUIState,
     area: Rect,
 ) {
+    let sym_refs = data.symbol_references(elf_summ, only_unresolved);
     match &elf_summ.binary_type {
         BinaryType::Static => {
             let w = Paragraph::new("No dynamic symbols (static binary)");
             f.render_widget(w, area);
         }
-        BinaryType::Dynamic(dyn_data) if dyn_data.dynamic_symbol_refs.is_empty() => {
+        BinaryType::Dynamic(_) if sym_refs.is_empty() && only_unresolved => {
+            let w = Paragraph::new("No unresolved dynamic symbols");
+            f.render_widget(w, area);
+        }
+        BinaryType::Dynamic(_) if sym_refs.is_empty() => {
             let w = Paragraph::new("No dynamic symbols");
             f.render_widget(w, area);
         }
-        BinaryType::Dynamic(dyn_data) => {
+        BinaryType::Dynamic(_) => {
+            let cols = columns::reference_columns(&data.symbol_columns);
+            let widths: Vec<Constraint> = cols.iter().map(|c| column_width(*c)).collect();
             let mut dyn_sym_data = Vec::new();
 
-            for sym_ref in &dyn_data.dynamic_symbol_refs {
-                let provider = resolutions
-                    .get(&sym_ref.symbol)
-                    .map_or(PathBuf::from("<Unresolved>"), |elf| elf.filename.clone());
-                dyn_sym_data.push(Row::new(vec![
-                    format!("{:?}", sym_ref.type_),
-                    format!("{:?}", sym_ref.binding),
-                    String::from(&sym_ref.symbol.name),
-                    provider.to_string_lossy().into(),
-                ]));
+            for sym_ref in sym_refs {
+                let provider = match data.symbol_resolutions.get(&sym_ref.symbol) {
+                    Some(path) => path.to_string_lossy().into_owned(),
+                    None => data
+                        .unresolved_symbols
+                        .get(&sym_ref.symbol)
+                        .map_or(String::from("<Unresolved>"), |reason| reason.to_string()),
+                };
+                dyn_sym_data.push(
+                    Row::new(columns::reference_row(
+                        sym_ref,
+                        &provider,
+                        &cols,
+                        data.demangle_style,
+                    ))
+                    .style(binding_style(&data.theme, sym_ref.binding)),
+                );
             }
 
             let dyn_sym_view = Table::new(dyn_sym_data)
                 .column_spacing(1)
-                .widths(&[
-                    Constraint::Min(12),
-                    Constraint::Min(12),
-                    Constraint::Length(40),
-                    Constraint::Length(40),
-                ])
+                .widths(&widths)
                 .block(
                     Block::default()
                         .title("Referenced Dynamic Symbols")
@@ -105,8 +311,8 @@ fn draw_dynamic_dependencies<B: Backend>(
                 .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
                 .highlight_symbol(">>")
                 .header(
-                    Row::new(vec!["Type", "Binding", "Symbol", "Provided By"])
-                        .style(Style::default().fg(Color::Yellow))
+                    Row::new(columns::headers(&cols))
+                        .style(data.theme.header)
                         .bottom_margin(1),
                 );
             f.render_stateful_widget(
@@ -118,66 +324,252 @@ fn draw_dynamic_dependencies<B: Backend>(
     }
 }
 
+/// Draw the binaries that reference the selected defined symbol
+fn draw_symbol_referrers<B: Backend>(f: &mut Frame<B>, referrers: Option<&[&Path]>, area: Rect) {
+    let items: Vec<ListItem> = match referrers {
+        None => vec![ListItem::new("Select a symbol")],
+        Some([]) => vec![ListItem::new("Not referenced in the closure")],
+        Some(bins) => bins
+            .iter()
+            .map(|bin| ListItem::new(bin.to_string_lossy()))
+            .collect(),
+    };
+    let w = List::new(items).block(
+        Block::default()
+            .title("Referenced By")
+            .borders(Borders::ALL),
+    );
+    f.render_widget(w, area);
+}
+
+/// Describe the binaries other than `elf_summ` that define `sym` (e.g.,
+/// `libfoo.so, libbar.so`), which it could interpose (or be interposed by)
+fn other_definers(
+    elf_summ: &ElfSummary,
+    sym: &VersionedSymbol,
+    definers: &BTreeMap<VersionedSymbol, Vec<PathBuf>>,
+) -> String {
+    definers
+        .get(sym)
+        .map_or(&[][..], |bins| bins.as_slice())
+        .iter()
+        .filter(|bin| **bin != elf_summ.filename)
+        .map(|bin| bin.file_name().unwrap_or(bin.as_os_str()).to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn draw_defined_dynamic_symbols<B: Backend>(
     f: &mut Frame<B>,
+    data: &StaticAppData,
     elf_summ: &ElfSummary,
+    referrers: Option<&[&Path]>,
+    filter_exports: bool,
     ui_state: &mut BinaryUIState,
     area: Rect,
 ) {
+    let defined = data.defined_symbols(elf_summ, filter_exports);
     match &elf_summ.binary_type {
         BinaryType::Static => {
             let w = Paragraph::new("No dynamic symbols (static binary)");
             f.render_widget(w, area);
         }
-        BinaryType::Dynamic(dyn_data) if dyn_data.provided_dynamic_symbols.is_empty() => {
-            let w = Paragraph::new("No dynamic symbols defined");
-            f.render_widget(w, area);
+        BinaryType::Dynamic(_) if defined.is_empty() => {
+            let msg = if filter_exports {
+                "No dynamic symbols of the selected types defined"
+            } else {
+                "No dynamic symbols defined"
+            };
+            f.render_widget(Paragraph::new(msg), area);
         }
-        BinaryType::Dynamic(dyn_data) => {
+        BinaryType::Dynamic(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
+                .split(area);
+            let cols = columns::definition_columns(&data.symbol_columns);
+            // The symbols are annotated with any other binaries that define
+            // them, since load order determines which definition is used
+            let mut widths: Vec<Constraint> = cols.iter().map(|c| column_width(*c)).collect();
+            widths.push(Constraint::Min(20));
+            let mut headers = columns::headers(&cols);
+            headers.push("Also In");
             let mut defined_sym_data = Vec::new();
+            let title = if filter_exports {
+                "Defined Dynamic Symbols (filtered by type)"
+            } else {
+                "Defined Dynamic Symbols"
+            };
 
-            for sym_def in &dyn_data.provided_dynamic_symbols {
-                defined_sym_data.push(Row::new(vec![
-                    format!("{:#x}", sym_def.address),
-                    format!("{}", sym_def.size),
-                    format!("{:?}", sym_def.type_),
-                    format!("{:?}", sym_def.binding),
-                    String::from(&sym_def.symbol.name),
-                ]));
+            for sym_def in defined {
+                let mut cells = columns::definition_row(
+                    elf_summ,
+                    sym_def,
+                    &cols,
+                    data.human_sizes,
+                    data.demangle_style,
+                );
+                cells.push(other_definers(
+                    elf_summ,
+                    &sym_def.symbol,
+                    &data.symbol_definers,
+                ));
+                defined_sym_data
+                    .push(Row::new(cells).style(binding_style(&data.theme, sym_def.binding)));
             }
 
             let defined_sym_view = Table::new(defined_sym_data)
                 .column_spacing(1)
-                .widths(&[
-                    Constraint::Min(11),
-                    Constraint::Min(5),
-                    Constraint::Min(12),
-                    Constraint::Min(12),
-                    Constraint::Length(40),
-                ])
-                .block(
-                    Block::default()
-                        .title("Defined Dynamic Symbols")
-                        .borders(Borders::ALL),
-                )
+                .widths(&widths)
+                .block(Block::default().title(title).borders(Borders::ALL))
                 .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
                 .highlight_symbol(">>")
-                .header(
-                    Row::new(vec!["Address", "Size", "Type", "Binding", "Symbol"])
-                        .style(Style::default().fg(Color::Yellow))
-                        .bottom_margin(1),
-                );
+                .header(Row::new(headers).style(data.theme.header).bottom_margin(1));
             f.render_stateful_widget(
                 defined_sym_view,
-                area,
+                chunks[0],
                 &mut ui_state.defined_dynamic_table_state,
             );
+            draw_symbol_referrers(f, referrers, chunks[1]);
         }
     }
 }
 
+fn draw_init_fini<B: Backend>(
+    f: &mut Frame<B>,
+    elf_summ: &ElfSummary,
+    theme: &Theme,
+    ui_state: &mut BinaryUIState,
+    area: Rect,
+) {
+    let funcs = match &elf_summ.binary_type {
+        BinaryType::Static => Vec::new(),
+        BinaryType::Dynamic(dyn_data) => dyn_data.init_fini_functions(),
+    };
+    if funcs.is_empty() {
+        let w = Paragraph::new("No initialization or finalization functions");
+        f.render_widget(w, area);
+        return;
+    }
+
+    let mut init_fini_data = Vec::new();
+    for (source, func) in funcs {
+        init_fini_data.push(Row::new(vec![
+            source.to_string(),
+            elf_summ.format_address(func.address),
+            func.symbol
+                .clone()
+                .unwrap_or_else(|| String::from("<Unknown>")),
+        ]));
+    }
+
+    let init_fini_view = Table::new(init_fini_data)
+        .column_spacing(1)
+        .widths(&[
+            Constraint::Min(14),
+            Constraint::Min(18),
+            Constraint::Length(40),
+        ])
+        .block(
+            Block::default()
+                .title("Initialization and Finalization Functions (in execution order)")
+                .borders(Borders::ALL),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+        .highlight_symbol(">>")
+        .header(
+            Row::new(vec!["Source", "Address", "Symbol"])
+                .style(theme.header)
+                .bottom_margin(1),
+        );
+    f.render_stateful_widget(init_fini_view, area, &mut ui_state.init_fini_table_state);
+}
+
+/// The defined dynamic symbol selected in the table for the given binary, if any
+fn selected_definition<'a>(app: &App, elf_summ: &'a ElfSummary) -> Option<&'a VersionedSymbol> {
+    let idx = app
+        .mutable_app_data
+        .binary_ui_state
+        .get(&elf_summ.filename)?
+        .defined_dynamic_table_state
+        .selected()?;
+    let filter_exports = app.mutable_app_data.filter_exports;
+    app.static_app_data
+        .defined_symbols(elf_summ, filter_exports)
+        .get(idx)
+        .map(|sym_def| &sym_def.symbol)
+}
+
+fn section_type_name(type_: u32) -> String {
+    let name = match type_ {
+        object::elf::SHT_NULL => "NULL",
+        object::elf::SHT_PROGBITS => "PROGBITS",
+        object::elf::SHT_SYMTAB => "SYMTAB",
+        object::elf::SHT_STRTAB => "STRTAB",
+        object::elf::SHT_RELA => "RELA",
+        object::elf::SHT_HASH => "HASH",
+        object::elf::SHT_DYNAMIC => "DYNAMIC",
+        object::elf::SHT_NOTE => "NOTE",
+        object::elf::SHT_NOBITS => "NOBITS",
+        object::elf::SHT_REL => "REL",
+        object::elf::SHT_DYNSYM => "DYNSYM",
+        object::elf::SHT_INIT_ARRAY => "INIT_ARRAY",
+        object::elf::SHT_FINI_ARRAY => "FINI_ARRAY",
+        object::elf::SHT_GNU_HASH => "GNU_HASH",
+        object::elf::SHT_GNU_VERSYM => "VERSYM",
+        object::elf::SHT_GNU_VERDEF => "VERDEF",
+        object::elf::SHT_GNU_VERNEED => "VERNEED",
+        other => return format!("{:#x}", other),
+    };
+    name.to_string()
+}
+
+fn draw_sections<B: Backend>(
+    f: &mut Frame<B>,
+    elf_summ: &ElfSummary,
+    theme: &Theme,
+    ui_state: &mut BinaryUIState,
+    area: Rect,
+) {
+    let mut section_data = Vec::new();
+    for sec in &elf_summ.sections {
+        // Compressed sections are typically debug information
+        let name = if sec.is_compressed() {
+            format!("{} (compressed)", sec.name)
+        } else {
+            sec.name.clone()
+        };
+        section_data.push(Row::new(vec![
+            name,
+            section_type_name(sec.type_),
+            elf_summ.format_address(sec.address),
+            format!("{:#x}", sec.offset),
+            format!("{}", sec.size),
+        ]));
+    }
+
+    let section_view = Table::new(section_data)
+        .column_spacing(1)
+        .widths(&[
+            Constraint::Length(32),
+            Constraint::Min(10),
+            Constraint::Min(18),
+            Constraint::Min(10),
+            Constraint::Min(10),
+        ])
+        .block(Block::default().title("Sections").borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+        .highlight_symbol(">>")
+        .header(
+            Row::new(vec!["Name", "Type", "Address", "Offset", "Size"])
+                .style(theme.header)
+                .bottom_margin(1),
+        );
+    f.render_stateful_widget(section_view, area, &mut ui_state.sections_table_state);
+}
+
 fn draw_selected_binary<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
-    match app.selected_binary() {
+    match app.static_app_data.selected_binary(&app.mutable_app_data) {
         None => {}
         Some(elf_summ) => {
             let chunks = Layout::default()
@@ -185,7 +577,18 @@ fn draw_selected_binary<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect)
                 .constraints([Constraint::Length(3), Constraint::Min(40)].as_ref())
                 .split(area);
 
-            let resolutions = &app.static_app_data.symbol_resolutions;
+            let data = &app.static_app_data;
+            let requested_name = data.selected_dependency_name(&app.mutable_app_data);
+            // The skipped libraries are only relevant to the root binary
+            let incompatible = if requested_name.is_none() {
+                &data.resolution.incompatible[..]
+            } else {
+                &[]
+            };
+            let referrers =
+                selected_definition(app, elf_summ).map(|def| data.referrers(elf_summ, def));
+            let only_unresolved = app.mutable_app_data.only_unresolved;
+            let filter_exports = app.mutable_app_data.filter_exports;
             let ui_state = app.mutable_app_data.binary_ui_state(elf_summ);
             let titles = ui_state
                 .tab_state
@@ -195,7 +598,7 @@ fn draw_selected_binary<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect)
                 .collect();
             let tabs = Tabs::new(titles)
                 .block(Block::default().title("Binary Views").borders(Borders::ALL))
-                .highlight_style(Style::default().fg(Color::Yellow))
+                .highlight_style(data.theme.header)
                 .select(ui_state.tab_state.selected_tab)
                 .divider(Span::from("|"));
 
@@ -203,27 +606,242 @@ fn draw_selected_binary<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect)
 
             match ui_state.tab_state.tab_labels[ui_state.tab_state.selected_tab] {
                 InfoTabLabels::Overview => {
-                    draw_binary_overview(f, elf_summ, chunks[1]);
+                    draw_binary_overview(
+                        f,
+                        elf_summ,
+                        requested_name,
+                        incompatible,
+                        &data.theme,
+                        chunks[1],
+                    );
                 }
                 InfoTabLabels::DynamicDependencies => {
-                    draw_dynamic_dependencies(f, elf_summ, resolutions, ui_state, chunks[1]);
+                    draw_dynamic_dependencies(
+                        f,
+                        data,
+                        elf_summ,
+                        only_unresolved,
+                        ui_state,
+                        chunks[1],
+                    );
                 }
                 InfoTabLabels::DefinedDynamicSymbols => {
-                    draw_defined_dynamic_symbols(f, elf_summ, ui_state, chunks[1]);
+                    draw_defined_dynamic_symbols(
+                        f,
+                        data,
+                        elf_summ,
+                        referrers.as_deref(),
+                        filter_exports,
+                        ui_state,
+                        chunks[1],
+                    );
                 }
+                InfoTabLabels::InitFini => {
+                    draw_init_fini(f, elf_summ, &data.theme, ui_state, chunks[1]);
+                }
+                InfoTabLabels::Sections => {
+                    draw_sections(f, elf_summ, &data.theme, ui_state, chunks[1]);
+                }
+            }
+        }
+    }
+}
+
+/// Draw the command palette over the middle of `area`
+fn draw_command_palette<B: Backend>(
+    f: &mut Frame<B>,
+    palette: &CommandPalette,
+    theme: &Theme,
+    area: Rect,
+) {
+    let matches = palette.matches();
+    let width = std::cmp::min(area.width, 60);
+    // Leave room for the borders
+    let height = std::cmp::min(area.height, matches.len() as u16 + 2);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 3,
+        width,
+        height,
+    );
+
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|c| ListItem::new(c.to_string()))
+        .collect();
+    let mut state = ListState::default();
+    if !matches.is_empty() {
+        state.select(Some(palette.selected));
+    }
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Command: {}", palette.query))
+                .borders(Borders::ALL),
+        )
+        .highlight_style(theme.header)
+        .highlight_symbol("> ");
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// The attributes of a symbol shown in the detail popup, as (label, value) pairs
+fn symbol_details(data: &StaticAppData, selected: &SelectedSymbol) -> Vec<(&'static str, String)> {
+    let (symbol, type_, binding, visibility) = match selected {
+        SelectedSymbol::Reference(r) => (&r.symbol, r.type_, r.binding, r.visibility),
+        SelectedSymbol::Definition(_, d) => (&d.symbol, d.type_, d.binding, d.visibility),
+    };
+    let mut details = vec![
+        ("Name", symbol.name.clone()),
+        (
+            "Demangled",
+            demangle::demangle(&symbol.name, data.demangle_style),
+        ),
+        ("Version", symbol.version.clone().unwrap_or_default()),
+        ("Type", format!("{:?}", type_)),
+        ("Binding", format!("{:?}", binding)),
+        ("Visibility", format!("{:?}", visibility)),
+    ];
+    match selected {
+        SelectedSymbol::Reference(sym_ref) => match data.symbol_resolutions.get(&sym_ref.symbol) {
+            Some(path) => {
+                let build_id = data.binary(path).and_then(|bin| bin.build_id.clone());
+                details.push(("Provider", path.to_string_lossy().into_owned()));
+                details.push(("Build ID", build_id.unwrap_or_default()));
+            }
+            None => {
+                let reason = data
+                    .unresolved_symbols
+                    .get(&sym_ref.symbol)
+                    .map_or(String::from("<Unresolved>"), |reason| reason.to_string());
+                details.push(("Provider", reason));
             }
+        },
+        SelectedSymbol::Definition(elf_summ, sym_def) => {
+            details.push(("Size", columns::format_size(sym_def.size, false)));
+            details.push(("Address", elf_summ.format_address(sym_def.address)));
+            details.push(("Section", elf_summ.section_name(sym_def.section)));
         }
     }
+    details
+}
+
+/// Draw the attributes of the selected symbol in the middle of `area`
+///
+/// Long values (e.g., demangled C++ names) are wrapped rather than truncated
+fn draw_symbol_details<B: Backend>(
+    f: &mut Frame<B>,
+    data: &StaticAppData,
+    selected: &SelectedSymbol,
+    area: Rect,
+) {
+    const LABEL_WIDTH: usize = 12;
+    let details = symbol_details(data, selected);
+    let width = std::cmp::min(area.width, 100);
+    // Leave room for the borders
+    let inner_width = std::cmp::max(usize::from(width), 3) - 2;
+    let num_lines: usize = details
+        .iter()
+        .map(|(_, value)| (LABEL_WIDTH + value.len()).div_ceil(inner_width))
+        .sum();
+    let height = std::cmp::min(area.height, num_lines as u16 + 2);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let lines: Vec<Spans> = details
+        .into_iter()
+        .map(|(label, value)| {
+            Spans::from(vec![
+                Span::styled(
+                    format!("{:<width$}", label, width = LABEL_WIDTH),
+                    data.theme.header,
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("Symbol (Esc to close)")
+                .borders(Borders::ALL),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+/// The footer describing the selected binary and tab, the active filters, and
+/// the most important keys
+fn draw_footer<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = app.static_app_data.theme;
+    let mut context = Vec::new();
+    if let Some(elf_summ) = app.static_app_data.selected_binary(&app.mutable_app_data) {
+        context.push(elf_summ.filename.as_path().to_string_lossy().into_owned());
+        let ui_state = app.mutable_app_data.binary_ui_state(elf_summ);
+        context.push(ui_state.tab_state.selected_label().to_string());
+    }
+    if app.mutable_app_data.only_unresolved {
+        context.push(String::from("only unresolved"));
+    }
+    if app.mutable_app_data.filter_exports {
+        context.push(String::from("only exports"));
+    }
+    let footer = Spans::from(vec![
+        Span::raw(context.join(" | ")),
+        Span::styled(
+            "  t exports  u unresolved  : address  C-q quit",
+            theme.muted,
+        ),
+    ]);
+    f.render_widget(Paragraph::new(footer), area);
 }
 
 pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    // Reserve the bottom line for the footer
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(f.size());
+    draw_footer(f, app, rows[1]);
+
+    // Reserve a line above the footer for the address being typed or the
+    // status message, if there is one
+    let mut main_area = rows[0];
+    let status_line = match &app.mutable_app_data.address_input {
+        Some(input) => Some(format!("Address: {}", input)),
+        None => app.mutable_app_data.status_message.clone(),
+    };
+    if let Some(msg) = status_line {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+            .split(main_area);
+        main_area = rows[0];
+        f.render_widget(Paragraph::new(msg.as_str()), rows[1]);
+    }
+
     // Set up a two column layout; the left will be the list of binary images,
     // while the right will be details for the selected image
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Ratio(1, 4), Constraint::Ratio(3, 4)].as_ref())
-        .split(f.size());
+        .split(main_area);
 
     draw_binary_list_sidebar(f, app, chunks[0]);
     draw_selected_binary(f, app, chunks[1]);
+
+    if app.mutable_app_data.show_symbol_details {
+        let data = &app.static_app_data;
+        if let Some(selected) = data.selected_symbol(&app.mutable_app_data) {
+            draw_symbol_details(f, data, &selected, main_area);
+        }
+    }
+    if let Some(palette) = &app.mutable_app_data.command_palette {
+        draw_command_palette(f, palette, &app.static_app_data.theme, main_area);
+    }
 }
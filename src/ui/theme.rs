@@ -0,0 +1,75 @@
+use std::env;
+use tui::style::{Color, Modifier, Style};
+
+use crate::options::ColorMode;
+
+/// The styles used by the interactive UI
+///
+/// All of the colors used by the `draw_*` functions come from here, so that
+/// they can be chosen to suit the colors that the terminal supports
+#[derive(Copy, Clone, Debug)]
+pub struct Theme {
+    /// Table headers and the selected tab
+    pub header: Style,
+    /// Warnings about the binary (e.g., inconsistent hash tables)
+    pub warning: Style,
+    /// The marker for dependencies that were found
+    pub resolved: Style,
+    /// The marker for dependencies that could not be found
+    pub unresolved: Style,
+    /// Secondary information (e.g., where a dependency was found)
+    pub muted: Style,
+    pub local_symbol: Style,
+    pub weak_symbol: Style,
+}
+
+/// Guess the colors supported by the terminal from the environment
+///
+/// Terminals that support 24 bit color conventionally set `COLORTERM`, while
+/// 256 color support is advertised through `TERM` (e.g., `xterm-256color`)
+fn detect_color_mode() -> ColorMode {
+    match env::var("COLORTERM") {
+        Ok(val) if val == "truecolor" || val == "24bit" => return ColorMode::Truecolor,
+        _ => {}
+    }
+    match env::var("TERM") {
+        Ok(term) if term.contains("256color") => ColorMode::Ansi256,
+        _ => ColorMode::Ansi16,
+    }
+}
+
+impl Theme {
+    pub fn new(mode: ColorMode) -> Self {
+        let mode = match mode {
+            ColorMode::Auto => detect_color_mode(),
+            mode => mode,
+        };
+        // Each palette is (yellow, red, green, gray)
+        let (yellow, red, green, gray) = match mode {
+            ColorMode::Auto | ColorMode::Ansi16 => {
+                (Color::Yellow, Color::Red, Color::Green, Color::DarkGray)
+            }
+            ColorMode::Ansi256 => (
+                Color::Indexed(220),
+                Color::Indexed(196),
+                Color::Indexed(34),
+                Color::Indexed(244),
+            ),
+            ColorMode::Truecolor => (
+                Color::Rgb(250, 200, 40),
+                Color::Rgb(230, 60, 60),
+                Color::Rgb(60, 190, 90),
+                Color::Rgb(128, 128, 128),
+            ),
+        };
+        Theme {
+            header: Style::default().fg(yellow),
+            warning: Style::default().fg(red),
+            resolved: Style::default().fg(green),
+            unresolved: Style::default().fg(red),
+            muted: Style::default().fg(gray),
+            local_symbol: Style::default().add_modifier(Modifier::DIM),
+            weak_symbol: Style::default().fg(yellow),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+extern int fixture_value;
+int fixture_add(int a, int b);
+
+void _start(void) {
+    fixture_add(fixture_value, 1);
+    for (;;) {
+    }
+}
@@ -0,0 +1 @@
+int inner_value = 7;